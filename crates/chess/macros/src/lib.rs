@@ -1,12 +1,23 @@
+extern crate alloc;
+
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::quote;
+use std::ops::Range;
 use syn::{
     parse::{Parse, ParseStream},
     parse_macro_input,
     token::Underscore,
-    Error, Ident, Result,
+    Error, Ident, LitStr, Result, Token,
 };
 
+#[allow(dead_code)]
+#[path = "../../src/notation.rs"]
+mod notation;
+#[allow(dead_code)]
+#[path = "../../src/offsets.rs"]
+mod offsets;
+
 fn namespace(span: proc_macro2::Span) -> Ident {
     let crate_name = std::env::var("CARGO_PKG_NAME").unwrap();
     if crate_name == "chess" {
@@ -22,160 +33,250 @@ impl Parse for Board {
     fn parse(input: ParseStream) -> Result<Self> {
         let ns = namespace(input.span());
         let mut pieces: Vec<proc_macro2::TokenStream> = vec![];
-        while pieces.len() < 64 {
+        let mut placement: notation::Placement = [None; 64];
+        let mut spans: Vec<Span> = vec![];
+        while spans.len() < 64 {
             let lookahead = input.lookahead1();
-            let col = (pieces.len() % 8) as u8;
-            let row = (pieces.len() / 8) as u8;
+            let col = (spans.len() % 8) as u8;
+            let row = (spans.len() / 8) as u8;
             if lookahead.peek(Ident) {
-                let span = input.span();
                 let ident: Ident = input.parse()?;
-                match ident.to_string().as_str() {
-                    "p" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Pawn, #ns::PieceColor::White)),
-                            );
-                        });
-                    }
-                    "r" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Rook, #ns::PieceColor::White)),
-                            );
-                        });
-                    }
-                    "n" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Knight, #ns::PieceColor::White)),
-                            );
-                        });
-                    }
-                    "b" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Bishop, #ns::PieceColor::White)),
-                            );
-                        });
-                    }
-                    "q" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Queen, #ns::PieceColor::White)),
-                            );
-                        });
-                    }
-                    "k" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::King, #ns::PieceColor::White)),
-                            );
-                        });
-                    }
-                    "P" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Pawn, #ns::PieceColor::Black)),
-                            );
-                        });
-                    }
-                    "R" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Rook, #ns::PieceColor::Black)),
-                            );
-                        });
-                    }
-                    "N" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Knight, #ns::PieceColor::Black)),
-                            );
-                        });
-                    }
-                    "B" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Bishop, #ns::PieceColor::Black)),
-                            );
-                        });
-                    }
-                    "Q" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::Queen, #ns::PieceColor::Black)),
-                            );
-                        });
-                    }
-                    "K" => {
-                        pieces.push(quote! {
-                            board.set_piece(
-                                #ns::Position(#col, #row),
-                                Some(#ns::Piece::new(#ns::PieceKind::King, #ns::PieceColor::Black)),
-                            );
-                        });
-                    }
-                    _ => {
-                        return Err(Error::new(span, "invalid chess board character"));
-                    }
-                }
+                let name = ident.to_string();
+                let piece = name
+                    .chars()
+                    .next()
+                    .filter(|c| name.len() == 1 && notation::is_piece(*c))
+                    .ok_or_else(|| Error::new(ident.span(), "invalid chess board character"))?;
+                // board! uses lowercase for White, the opposite of FEN
+                let fen_char = if piece.is_ascii_lowercase() {
+                    piece.to_ascii_uppercase()
+                } else {
+                    piece.to_ascii_lowercase()
+                };
+                placement[spans.len()] = Some(fen_char);
+                let piece = piece_tokens(&ns, fen_char);
+                pieces.push(quote! {
+                    board.set_piece(#ns::Position(#col, #row), Some(#piece));
+                });
+                spans.push(ident.span());
             } else if lookahead.peek(Underscore) {
-                let _: Underscore = input.parse()?;
+                let underscore: Underscore = input.parse()?;
                 pieces.push(quote! {
                     board.set_piece(#ns::Position(#col, #row), None);
                 });
+                spans.push(underscore.span);
             } else {
                 return Err(lookahead.error());
             }
         }
+        let mut setup: Vec<proc_macro2::TokenStream> = vec![];
+        let mut clauses: Vec<String> = vec![];
+        let mut turn = notation::Color::White;
+        let mut castling: Option<(notation::Castling, Span)> = None;
+        let mut en_passant: Option<(notation::Square, Span)> = None;
+        while !input.is_empty() {
+            if input.peek(Token![,]) {
+                let _: Token![,] = input.parse()?;
+                continue;
+            }
+            let span = input.span();
+            let clause: Ident = input.parse()?;
+            let name = clause.to_string();
+            let kind = if name.ends_with("_to_move") {
+                "turn"
+            } else {
+                name.as_str()
+            };
+            if clauses.iter().any(|c| c == kind) {
+                return Err(Error::new(span, format!("duplicate `{}` clause", kind)));
+            }
+            clauses.push(kind.to_owned());
+            match name.as_str() {
+                "white_to_move" => {
+                    turn = notation::Color::White;
+                    setup.push(quote! { builder.turn(#ns::PieceColor::White); });
+                }
+                "black_to_move" => {
+                    turn = notation::Color::Black;
+                    setup.push(quote! { builder.turn(#ns::PieceColor::Black); });
+                }
+                "castling" => {
+                    let _: Token![:] = input.parse()?;
+                    let span = input.span();
+                    let value = if input.peek(Token![-]) {
+                        let _: Token![-] = input.parse()?;
+                        "-".to_owned()
+                    } else {
+                        let ident: Ident = input.parse()?;
+                        ident.to_string()
+                    };
+                    let rights = notation::parse_castling(&value)
+                        .ok_or_else(|| Error::new(span, "invalid castling rights"))?;
+                    castling = Some((rights, span));
+                    let rights = castling_tokens(&ns, rights);
+                    setup.push(quote! { builder.castling_rights(#rights); });
+                }
+                "ep" => {
+                    let _: Token![:] = input.parse()?;
+                    let span = input.span();
+                    let (col, row) = parse_square(input)?;
+                    en_passant = Some(((col, row), span));
+                    setup.push(quote! {
+                        builder.en_passant_target(Some(#ns::Position(#col, #row)));
+                    });
+                }
+                _ => {
+                    return Err(Error::new(
+                        span,
+                        "expected `white_to_move`, `black_to_move`, `castling: ..` or `ep: ..`",
+                    ));
+                }
+            }
+        }
+        if setup.is_empty() {
+            return Ok(Board(TokenStream::from(quote! {
+                {
+                    let mut board = #ns::Board::new();
+                    #(#pieces)*
+                    board
+                }
+            })));
+        }
+        let problems = notation::validate(
+            &placement,
+            turn,
+            castling.map_or([false; 4], |(rights, _)| rights),
+            en_passant.map(|(square, _)| square),
+        );
+        check(problems, |problem| {
+            let span = match problem {
+                notation::Problem::TooManyKings(_, square)
+                | notation::Problem::PawnOnBackRank(square)
+                | notation::Problem::OpponentInCheck(_, square) => spans[notation::index(square)],
+                notation::Problem::InvalidCastling(_) => {
+                    castling.map_or_else(Span::call_site, |(_, span)| span)
+                }
+                notation::Problem::InvalidEnPassant(_) => {
+                    en_passant.map_or_else(Span::call_site, |(_, span)| span)
+                }
+                _ => Span::call_site(),
+            };
+            Error::new(span, problem_message(problem))
+        })?;
         Ok(Board(TokenStream::from(quote! {
             {
                 let mut board = #ns::Board::new();
                 #(#pieces)*
-                board
+                let mut builder = #ns::BoardBuilder::from_board(&board);
+                #(#setup)*
+                match builder.build() {
+                    Ok(board) => board,
+                    Err(errors) => panic!("invalid board: {:?}", errors),
+                }
             }
         })))
     }
 }
 
+fn piece_tokens(ns: &Ident, fen_char: char) -> proc_macro2::TokenStream {
+    let kind = match fen_char.to_ascii_lowercase() {
+        'p' => quote! { Pawn },
+        'r' => quote! { Rook },
+        'n' => quote! { Knight },
+        'b' => quote! { Bishop },
+        'q' => quote! { Queen },
+        _ => quote! { King },
+    };
+    let color = if fen_char.is_ascii_uppercase() {
+        quote! { White }
+    } else {
+        quote! { Black }
+    };
+    quote! { #ns::Piece::new(#ns::PieceKind::#kind, #ns::PieceColor::#color) }
+}
+
+fn castling_tokens(ns: &Ident, castling: notation::Castling) -> proc_macro2::TokenStream {
+    let mut rights: Vec<proc_macro2::TokenStream> = vec![];
+    for (right, has) in castling.iter().enumerate() {
+        if !has {
+            continue;
+        }
+        let (color, side) = match notation::CASTLING_CHARS[right] {
+            'K' => (quote! { White }, quote! { King }),
+            'Q' => (quote! { White }, quote! { Queen }),
+            'k' => (quote! { Black }, quote! { King }),
+            _ => (quote! { Black }, quote! { Queen }),
+        };
+        rights.push(quote! {
+            rights.set(#ns::PieceColor::#color, #ns::CastlingSide::#side, true);
+        });
+    }
+    quote! {
+        {
+            let mut rights = #ns::CastlingRights::none();
+            #(#rights)*
+            rights
+        }
+    }
+}
+
+fn problem_message(problem: notation::Problem) -> String {
+    match problem {
+        notation::Problem::MissingKing(color) => format!("{:?} has no king", color),
+        notation::Problem::TooManyKings(color, _) => {
+            format!("{:?} has more than one king", color)
+        }
+        notation::Problem::TooManyPawns(color) => format!("{:?} has more than eight pawns", color),
+        notation::Problem::TooManyPieces(color) => {
+            format!("{:?} has more than sixteen pieces", color)
+        }
+        notation::Problem::PawnOnBackRank(_) => "pawn on back rank".to_owned(),
+        notation::Problem::InvalidCastling(right) => format!(
+            "castling right `{}` needs an unmoved king and rook",
+            notation::CASTLING_CHARS[right]
+        ),
+        notation::Problem::InvalidEnPassant(_) => "invalid en passant target".to_owned(),
+        notation::Problem::OpponentInCheck(color, _) => {
+            format!("{:?} is in check but it is not their turn", color)
+        }
+    }
+}
+
+// A block keeps several `compile_error!`s valid in expression position.
+fn compile_errors(error: Error) -> TokenStream {
+    let errors = error.to_compile_error();
+    TokenStream::from(quote! { { #errors } })
+}
+
+fn check(
+    problems: Vec<notation::Problem>,
+    error: impl Fn(notation::Problem) -> Error,
+) -> Result<()> {
+    let mut errors = problems.into_iter().map(error);
+    let Some(mut error) = errors.next() else {
+        return Ok(());
+    };
+    for other in errors {
+        error.combine(other);
+    }
+    Err(error)
+}
+
 #[proc_macro]
 pub fn board(tokens: TokenStream) -> TokenStream {
-    let board = parse_macro_input!(tokens as Board);
-    board.0
+    match syn::parse::<Board>(tokens) {
+        Ok(board) => board.0,
+        Err(error) => compile_errors(error),
+    }
 }
 
 struct BoardMove(TokenStream);
 
-const COLS: [char; 8] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
-const ROWS: [char; 8] = ['8', '7', '6', '5', '4', '3', '2', '1'];
 fn parse_square(input: ParseStream) -> Result<(u8, u8)> {
     let span = input.span();
     let ident: Ident = input.parse()?;
-    let name = ident.to_string().to_ascii_lowercase();
-    if name.len() != 2 {
-        return Err(Error::new(span, "invalid chess move"));
-    }
-    let chars: Vec<char> = name.chars().collect();
-    let col = COLS
-        .iter()
-        .position(|c| *c == chars[0])
-        .expect("invalid chess move");
-    let row = ROWS
-        .iter()
-        .position(|c| *c == chars[1])
-        .expect("invalid chess move");
-    Ok((col as u8, row as u8))
+    notation::parse_square(&ident.to_string())
+        .ok_or_else(|| Error::new(span, "invalid chess square"))
 }
 
 impl Parse for BoardMove {
@@ -197,3 +298,214 @@ pub fn board_move(tokens: TokenStream) -> TokenStream {
     let board_move = parse_macro_input!(tokens as BoardMove);
     board_move.0
 }
+
+struct Fen(TokenStream);
+
+// Points at `range` of the literal's value, falling back to the whole literal
+// when the compiler can't resolve subspans (always the case on stable) or the
+// literal has escapes.
+fn subspan(lit: &LitStr, range: Range<usize>) -> Span {
+    let token = lit.token();
+    let source = token.to_string();
+    if source.starts_with('"') && source.len() == lit.value().len() + 2 {
+        if let Some(span) = token.subspan(range.start + 1..range.end + 1) {
+            return span;
+        }
+    }
+    lit.span()
+}
+
+// The column keeps the offending token findable where `subspan` can only
+// highlight the whole literal.
+fn fen_error(lit: &LitStr, range: Range<usize>, message: String) -> Error {
+    let column = range.start + 1;
+    Error::new(
+        subspan(lit, range),
+        format!("{} at column {}", message, column),
+    )
+}
+
+impl Parse for Fen {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ns = namespace(input.span());
+        let lit: LitStr = input.parse()?;
+        let fen = lit.value();
+        let mut fields = fen.split_whitespace().map(|field| {
+            let start = field.as_ptr() as usize - fen.as_ptr() as usize;
+            (field, start..start + field.len())
+        });
+        let mut field = |name: &str| {
+            fields
+                .next()
+                .ok_or_else(|| Error::new(lit.span(), format!("missing FEN field: {}", name)))
+        };
+        let (placement, placement_range) = field("placement")?;
+        let (turn, turn_range) = field("turn")?;
+        let (castling, castling_range) = field("castling")?;
+        let (en_passant, en_passant_range) = field("en passant")?;
+        let (halfmove_clock, halfmove_range) = fields.next().unwrap_or(("0", 0..0));
+        let (fullmove_number, fullmove_range) = fields.next().unwrap_or(("1", 0..0));
+        if let Some((field, range)) = fields.next() {
+            return Err(fen_error(
+                &lit,
+                range,
+                format!("unexpected FEN field `{}`", field),
+            ));
+        }
+        let error = |range: Range<usize>, message: String| fen_error(&lit, range, message);
+
+        let offset = placement_range.start;
+        let pieces =
+            notation::parse_placement(placement).map_err(
+                |placement_error| match placement_error {
+                    notation::PlacementError::RankCount(count) => error(
+                        placement_range.clone(),
+                        format!("invalid piece placement: expected 8 ranks, found {}", count),
+                    ),
+                    notation::PlacementError::InvalidPiece(range) => error(
+                        offset + range.start..offset + range.end,
+                        format!("invalid piece `{}`", &placement[range]),
+                    ),
+                    notation::PlacementError::InvalidRank(range) => error(
+                        offset + range.start..offset + range.end,
+                        format!("invalid rank `{}`: expected 8 squares", &placement[range]),
+                    ),
+                },
+            )?;
+        let turn_color = match turn {
+            "w" => notation::Color::White,
+            "b" => notation::Color::Black,
+            _ => {
+                return Err(error(
+                    turn_range,
+                    format!("invalid side to move `{}`", turn),
+                ))
+            }
+        };
+        let rights = notation::parse_castling(castling).ok_or_else(|| {
+            error(
+                castling_range.clone(),
+                format!("invalid castling rights `{}`", castling),
+            )
+        })?;
+        let target = if en_passant == "-" {
+            None
+        } else {
+            Some(notation::parse_square(en_passant).ok_or_else(|| {
+                error(
+                    en_passant_range.clone(),
+                    format!("invalid en passant target `{}`", en_passant),
+                )
+            })?)
+        };
+        let halfmove_clock: u32 = halfmove_clock.parse().map_err(|_| {
+            error(
+                halfmove_range,
+                format!("invalid halfmove clock `{}`", halfmove_clock),
+            )
+        })?;
+        let fullmove_number: u32 = fullmove_number
+            .parse()
+            .ok()
+            .filter(|number| *number > 0)
+            .ok_or_else(|| {
+                error(
+                    fullmove_range,
+                    format!("invalid fullmove number `{}`", fullmove_number),
+                )
+            })?;
+
+        let square_range = |square: notation::Square| {
+            let mut start = offset;
+            for (row, rank) in placement.split('/').enumerate() {
+                let mut col = 0;
+                for (at, c) in rank.char_indices() {
+                    if (col, row) == (square.0 as u32, square.1 as usize) {
+                        return start + at..start + at + 1;
+                    }
+                    col += c.to_digit(10).unwrap_or(1);
+                }
+                start += rank.len() + 1;
+            }
+            placement_range.clone()
+        };
+        check(
+            notation::validate(&pieces, turn_color, rights, target),
+            |problem| {
+                let range = match problem {
+                    notation::Problem::TooManyKings(_, square)
+                    | notation::Problem::PawnOnBackRank(square)
+                    | notation::Problem::OpponentInCheck(_, square) => square_range(square),
+                    notation::Problem::InvalidCastling(_) => castling_range.clone(),
+                    notation::Problem::InvalidEnPassant(_) => en_passant_range.clone(),
+                    _ => placement_range.clone(),
+                };
+                error(range, problem_message(problem))
+            },
+        )?;
+
+        let mut setup: Vec<proc_macro2::TokenStream> = vec![];
+        for (index, piece) in pieces.iter().enumerate() {
+            if let Some(piece) = piece {
+                let col = (index % 8) as u8;
+                let row = (index / 8) as u8;
+                let piece = piece_tokens(&ns, *piece);
+                setup.push(quote! {
+                    builder.piece(#ns::Position(#col, #row), Some(#piece));
+                });
+            }
+        }
+        let turn = match turn_color {
+            notation::Color::White => quote! { White },
+            notation::Color::Black => quote! { Black },
+        };
+        let castling = castling_tokens(&ns, rights);
+        let en_passant = match target {
+            Some((col, row)) => quote! { Some(#ns::Position(#col, #row)) },
+            None => quote! { None },
+        };
+
+        Ok(Fen(TokenStream::from(quote! {
+            {
+                let mut builder = #ns::BoardBuilder::new();
+                #(#setup)*
+                builder.turn(#ns::PieceColor::#turn);
+                builder.castling_rights(#castling);
+                builder.en_passant_target(#en_passant);
+                builder.halfmove_clock(#halfmove_clock);
+                builder.fullmove_number(#fullmove_number);
+                match builder.build() {
+                    Ok(board) => board,
+                    Err(errors) => panic!("invalid fen position: {:?}", errors),
+                }
+            }
+        })))
+    }
+}
+
+#[proc_macro]
+pub fn fen(tokens: TokenStream) -> TokenStream {
+    match syn::parse::<Fen>(tokens) {
+        Ok(fen) => fen.0,
+        Err(error) => compile_errors(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fen_error;
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    #[test]
+    fn fen_errors_report_the_column() {
+        let lit = LitStr::new(
+            "rnbqkbnr/ppxppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -",
+            Span::call_site(),
+        );
+        assert_eq!(
+            fen_error(&lit, 11..12, "invalid piece `x`".to_owned()).to_string(),
+            "invalid piece `x` at column 12"
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{
+    offsets, Board, BoardMove, Ic, Piece, PieceColor, PieceKind, Position, PositionOffset,
+};
+use alloc::{vec, vec::Vec};
+
+pub const KNIGHT_OFFSETS: [PositionOffset; 8] = position_offsets(offsets::KNIGHT);
+pub const KING_OFFSETS: [PositionOffset; 8] = position_offsets(offsets::KING);
+pub const ROOK_DIRECTIONS: [PositionOffset; 4] = position_offsets(offsets::ROOK);
+pub const BISHOP_DIRECTIONS: [PositionOffset; 4] = position_offsets(offsets::BISHOP);
+
+const fn position_offsets<const N: usize>(deltas: [(Ic, Ic); N]) -> [PositionOffset; N] {
+    let mut position_offsets = [PositionOffset(0, 0); N];
+    let mut index = 0;
+    while index < N {
+        position_offsets[index] = PositionOffset(deltas[index].0, deltas[index].1);
+        index += 1;
+    }
+    position_offsets
+}
+
+impl Board {
+    pub fn is_attacked(&self, position: Position, color: PieceColor) -> bool {
+        self.find_attacker(position, color, |_, _| true).is_some()
+    }
+
+    pub fn attackers(&self, position: Position, color: PieceColor) -> Vec<Position> {
+        let mut attackers = vec![];
+        self.find_attacker(position, color, |from, _| {
+            attackers.push(from);
+            false
+        });
+        attackers
+    }
+
+    pub fn attacker_count(&self, position: Position, color: PieceColor) -> usize {
+        let mut count = 0;
+        self.find_attacker(position, color, |_, _| {
+            count += 1;
+            false
+        });
+        count
+    }
+
+    pub(crate) fn least_valuable_attacker(
+        &self,
+        position: Position,
+        color: PieceColor,
+    ) -> Option<(Position, Piece)> {
+        let mut least: Option<(Position, Piece)> = None;
+        self.find_attacker(position, color, |from, piece| {
+            if least.map_or(true, |(_, least)| {
+                exchange_order(&piece) < exchange_order(&least)
+            }) {
+                least = Some((from, piece));
+            }
+            piece.kind() == PieceKind::Pawn
+        });
+        least
+    }
+
+    fn find_attacker(
+        &self,
+        position: Position,
+        color: PieceColor,
+        mut found: impl FnMut(Position, Piece) -> bool,
+    ) -> Option<Position> {
+        let mut check = |offset: PositionOffset, kinds: &[PieceKind]| {
+            if let Some(from) = position.offset(offset) {
+                if let Some(piece) = self.piece(from) {
+                    if piece.color() == color && kinds.contains(&piece.kind()) {
+                        return found(from, piece).then_some(from);
+                    }
+                }
+            }
+            None
+        };
+        let behind = -color.forward();
+        for offset in [PositionOffset(-1, behind), PositionOffset(1, behind)] {
+            if let Some(from) = check(offset, &[PieceKind::Pawn]) {
+                return Some(from);
+            }
+        }
+        for offset in KNIGHT_OFFSETS {
+            if let Some(from) = check(offset, &[PieceKind::Knight]) {
+                return Some(from);
+            }
+        }
+        for (directions, kind) in [
+            (BISHOP_DIRECTIONS, PieceKind::Bishop),
+            (ROOK_DIRECTIONS, PieceKind::Rook),
+        ] {
+            for direction in directions {
+                if let Some(distance) = self.first_piece_distance(position, direction) {
+                    let offset =
+                        PositionOffset(direction.col() * distance, direction.row() * distance);
+                    if let Some(from) = check(offset, &[kind, PieceKind::Queen]) {
+                        return Some(from);
+                    }
+                }
+            }
+        }
+        for offset in KING_OFFSETS {
+            if let Some(from) = check(offset, &[PieceKind::King]) {
+                return Some(from);
+            }
+        }
+        None
+    }
+
+    fn first_piece_distance(&self, position: Position, direction: PositionOffset) -> Option<Ic> {
+        let mut distance = 1;
+        let mut next = position.offset(direction);
+        while let Some(position) = next {
+            if self.piece(position).is_some() {
+                return Some(distance);
+            }
+            distance += 1;
+            next = position.offset(direction);
+        }
+        None
+    }
+
+    pub fn see(&self, board_move: BoardMove) -> i32 {
+        let mut board = *self;
+        let mover = match board.piece(board_move.from) {
+            Some(mover) => mover,
+            None => return 0,
+        };
+        let captured = match board.piece(board_move.to) {
+            Some(captured) => captured.value() as i32,
+            None if mover.kind() == PieceKind::Pawn
+                && board_move.from.col() != board_move.to.col() =>
+            {
+                board.set_piece(Position(board_move.to.col(), board_move.from.row()), None);
+                Piece::new(PieceKind::Pawn, mover.color().opposite()).value() as i32
+            }
+            None => 0,
+        };
+        let mut gains = vec![captured];
+        let mut on_target = mover;
+        let mut color = mover.color().opposite();
+        board.set_piece(board_move.from, None);
+        board.set_piece(board_move.to, Some(mover));
+        while let Some((from, attacker)) = board.least_valuable_attacker(board_move.to, color) {
+            if attacker.kind() == PieceKind::King {
+                let mut after = board;
+                after.set_piece(from, None);
+                after.set_piece(board_move.to, Some(attacker));
+                if after.is_attacked(board_move.to, color.opposite()) {
+                    break;
+                }
+            }
+            gains.push(on_target.value() as i32 - gains[gains.len() - 1]);
+            board.set_piece(from, None);
+            board.set_piece(board_move.to, Some(attacker));
+            on_target = attacker;
+            color = color.opposite();
+        }
+        while gains.len() > 1 {
+            let last = gains.pop().unwrap();
+            let previous = gains.len() - 1;
+            gains[previous] = -(-gains[previous]).max(last);
+        }
+        gains[0]
+    }
+}
+
+fn exchange_order(piece: &Piece) -> u8 {
+    match piece.kind() {
+        PieceKind::Pawn => 0,
+        PieceKind::Knight => 1,
+        PieceKind::Bishop => 2,
+        PieceKind::Rook => 3,
+        PieceKind::Queen => 4,
+        PieceKind::King => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PieceColor, Position};
+    use chess_macros::{board, board_move, fen};
+
+    #[test]
+    fn attacked_by_sliders_until_blocked() {
+        let board = board!(
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            r _ _ P _ _ _ b
+        );
+        assert!(board.is_attacked(Position(2, 7), PieceColor::White));
+        assert!(board.is_attacked(Position(3, 7), PieceColor::White));
+        assert!(!board.is_attacked(Position(4, 7), PieceColor::White));
+        assert!(board.is_attacked(Position(0, 0), PieceColor::White));
+        assert!(board.is_attacked(Position(1, 1), PieceColor::White));
+        assert!(board.is_attacked(Position(6, 6), PieceColor::White));
+    }
+
+    #[test]
+    fn attacked_by_pawns_diagonally() {
+        let board = board!(
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ P _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ p _ _ _
+            _ _ _ _ _ _ _ _
+        );
+        assert!(board.is_attacked(Position(3, 5), PieceColor::White));
+        assert!(board.is_attacked(Position(5, 5), PieceColor::White));
+        assert!(!board.is_attacked(Position(4, 5), PieceColor::White));
+        assert!(board.is_attacked(Position(2, 4), PieceColor::Black));
+        assert!(board.is_attacked(Position(4, 4), PieceColor::Black));
+        assert!(!board.is_attacked(Position(3, 4), PieceColor::Black));
+    }
+
+    #[test]
+    fn attackers_lists_every_attacker() {
+        let board = fen!("4k3/8/8/3p4/4P3/2N5/8/3RK3 w - - 0 1");
+        assert_eq!(
+            board.attackers(Position(3, 3), PieceColor::White),
+            vec![Position(4, 4), Position(2, 5), Position(3, 7)]
+        );
+        assert_eq!(board.attacker_count(Position(3, 3), PieceColor::White), 3);
+        assert!(board
+            .attackers(Position(3, 3), PieceColor::Black)
+            .is_empty());
+    }
+
+    #[test]
+    fn see_undefended_capture() {
+        let board = fen!("4k3/8/3n4/4P3/8/8/8/4K3 w - - 0 1");
+        assert_eq!(board.see(board_move!(e5 d6)), 3);
+    }
+
+    #[test]
+    fn see_losing_capture() {
+        let board = fen!("4k3/8/2p5/3p4/8/8/3R4/4K3 w - - 0 1");
+        assert_eq!(board.see(board_move!(d2 d5)), -4);
+    }
+
+    #[test]
+    fn see_counts_x_ray_attackers() {
+        let board = fen!("3r2k1/8/8/3p4/8/8/3R4/3RK3 w - - 0 1");
+        assert_eq!(board.see(board_move!(d2 d5)), 1);
+        let board = fen!("3r2k1/8/8/3p4/8/8/3R4/4K3 w - - 0 1");
+        assert_eq!(board.see(board_move!(d2 d5)), -4);
+    }
+
+    #[test]
+    fn see_stops_before_king_captures_into_defence() {
+        let board = fen!("6k1/8/8/3r4/4K3/8/3R4/8 w - - 0 1");
+        assert_eq!(board.see(board_move!(d2 d5)), 5);
+        let board = fen!("3r2k1/8/8/3r4/4K3/8/3R4/8 w - - 0 1");
+        assert_eq!(board.see(board_move!(d2 d5)), 5);
+        let board = fen!("3r2k1/3r4/8/3r4/4K3/8/3R4/8 w - - 0 1");
+        assert_eq!(board.see(board_move!(d2 d5)), 0);
+    }
+
+    #[test]
+    fn see_en_passant() {
+        let board = fen!("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+        assert_eq!(board.see(board_move!(e5 d6)), 1);
+    }
+}
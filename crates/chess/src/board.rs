@@ -1,12 +1,17 @@
 use crate::{
     constants::{COL_COUNT, ROW_COUNT, SQUARE_COUNT},
+    moves::ValidMoves,
     piece::PieceMovePlanner,
     rules::{
-        castle::Castle, en_passant::EnPassant, pawn_first_move::PawnFirstMove, promotion::Promotion,
+        castle::{Castle, CastlingRights},
+        en_passant::EnPassant,
+        pawn_first_move::PawnFirstMove,
+        promotion::Promotion,
     },
-    Ic, Piece, PieceColor, PieceKind, Position, Uc,
+    Piece, PieceColor, PieceKind, Position,
 };
-use std::fmt;
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
 
 #[derive(Clone, Copy)]
 pub struct Board {
@@ -15,6 +20,15 @@ pub struct Board {
     en_passant: EnPassant,
     pawn_first_move: PawnFirstMove,
     promotion: Promotion,
+    turn: PieceColor,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Board {
@@ -47,6 +61,29 @@ impl Board {
             en_passant: EnPassant::new(),
             pawn_first_move: PawnFirstMove::new(),
             promotion: Promotion::new(),
+            turn: PieceColor::White,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    pub(crate) fn from_parts(
+        pieces: [Option<Piece>; SQUARE_COUNT],
+        turn: PieceColor,
+        castling_rights: CastlingRights,
+        en_passant_target: Option<Position>,
+        halfmove_clock: u32,
+        fullmove_number: u32,
+    ) -> Self {
+        Self {
+            pieces,
+            castle: Castle::from_rights(castling_rights),
+            en_passant: EnPassant::from_target(en_passant_target),
+            pawn_first_move: PawnFirstMove::new(),
+            promotion: Promotion::new(),
+            turn,
+            halfmove_clock,
+            fullmove_number,
         }
     }
 
@@ -58,39 +95,85 @@ impl Board {
         self.pieces[position.index()]
     }
 
-    pub(crate) fn moves_internal(&self, color: PieceColor, king_check: bool) -> Vec<BoardMove> {
-        let mut all_moves = vec![];
-        for position in Position::all().iter() {
-            if let Some(piece) = &self.piece(*position) {
-                if piece.color() == color {
-                    let mut planner = PieceMovePlanner::new(self, *position, color);
-                    piece.moves(&mut planner);
-                    self.castle.moves(piece, &mut planner, king_check);
-                    self.pawn_first_move.moves(piece, &mut planner);
-                    self.en_passant.moves(piece, &mut planner);
-                    let mut moves = planner.moves();
-                    while let Some(board_move) = moves.pop() {
-                        if king_check {
-                            let mut board_clone = self.clone();
-                            board_clone.force_move(board_move);
-                            if !board_clone.check(color) {
-                                all_moves.push(board_move);
-                            }
-                        } else {
-                            all_moves.push(board_move);
-                        }
-                    }
-                }
-            }
+    pub fn turn(&self) -> PieceColor {
+        self.turn
+    }
+
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    pub fn castling_rights(&self) -> CastlingRights {
+        self.castle.rights(&self.pieces)
+    }
+
+    pub fn en_passant_target(&self) -> Option<Position> {
+        self.en_passant.target()
+    }
+
+    pub(crate) fn plan_moves(
+        &self,
+        position: Position,
+        piece: &Piece,
+    ) -> [Option<BoardMove>; SQUARE_COUNT] {
+        let mut planner = PieceMovePlanner::new(self, position, piece.color());
+        piece.moves(&mut planner);
+        self.castle.moves(piece, &mut planner);
+        self.pawn_first_move.moves(piece, &mut planner);
+        self.en_passant.moves(piece, &mut planner);
+        planner.into_moves()
+    }
+
+    pub(crate) fn is_legal(&self, board_move: BoardMove) -> bool {
+        if let Some(piece) = self.piece(board_move.from) {
+            let mut board_clone = *self;
+            board_clone.force_move(board_move);
+            !board_clone.check(piece.color())
+        } else {
+            false
         }
-        all_moves
+    }
+
+    pub fn moves(&self, color: PieceColor) -> ValidMoves<'_> {
+        ValidMoves::new(self, 0..SQUARE_COUNT, Some(color), None)
+    }
+
+    pub fn moves_from(&self, position: Position) -> ValidMoves<'_> {
+        ValidMoves::new(self, position.index()..position.index() + 1, None, None)
+    }
+
+    pub fn moves_to(&self, position: Position) -> ValidMoves<'_> {
+        ValidMoves::new(self, 0..SQUARE_COUNT, None, Some(position))
     }
 
     pub fn valid_moves(&self, color: PieceColor) -> Vec<BoardMove> {
-        self.moves_internal(color, true)
+        self.moves(color).collect()
+    }
+
+    pub fn valid_moves_from(&self, position: Position) -> Vec<BoardMove> {
+        self.moves_from(position).collect()
+    }
+
+    pub fn valid_moves_to(&self, position: Position) -> Vec<BoardMove> {
+        self.moves_to(position).collect()
     }
 
     pub fn force_move(&mut self, board_move: BoardMove) {
+        if let Some(piece) = self.piece(board_move.from) {
+            if piece.kind() == PieceKind::Pawn || self.piece(board_move.to).is_some() {
+                self.halfmove_clock = 0;
+            } else {
+                self.halfmove_clock += 1;
+            }
+            if piece.color() == PieceColor::Black {
+                self.fullmove_number += 1;
+            }
+            self.turn = piece.color().opposite();
+        }
         self.castle.apply_move(&board_move, &mut self.pieces);
         self.en_passant.apply_move(&board_move, &mut self.pieces);
         self.pieces[board_move.to.index()] = self.pieces[board_move.from.index()];
@@ -98,6 +181,15 @@ impl Board {
         self.promotion.apply_move(&mut self.pieces);
     }
 
+    pub fn null_move(&mut self) {
+        self.en_passant = EnPassant::new();
+        self.halfmove_clock += 1;
+        if self.turn == PieceColor::Black {
+            self.fullmove_number += 1;
+        }
+        self.turn = self.turn.opposite();
+    }
+
     pub fn apply_move(&mut self, board_move: BoardMove) -> bool {
         if self.is_valid_move(board_move) {
             self.force_move(board_move);
@@ -108,90 +200,41 @@ impl Board {
     }
 
     pub fn is_valid_move(&self, board_move: BoardMove) -> bool {
-        if let Some(piece) = &self.piece(board_move.from) {
-            let valid_moves = self.valid_moves(piece.color());
-            return valid_moves.into_iter().find(|m| *m == board_move).is_some();
-        } else {
-            false
-        }
+        let from = board_move.from.index();
+        ValidMoves::new(self, from..from + 1, None, Some(board_move.to)).any(|m| m == board_move)
     }
 
     pub fn check(&self, color: PieceColor) -> bool {
-        let opponents_moves = self.moves_internal(color.opposite(), false);
         for position in Position::all().iter() {
             if let Some(piece) = self.piece(*position) {
-                if piece.color() == color && piece.kind() == PieceKind::King {
-                    for opponents_move in opponents_moves.iter() {
-                        if opponents_move.to == *position {
-                            return true;
-                        }
-                    }
+                if piece.color() == color
+                    && piece.kind() == PieceKind::King
+                    && self.is_attacked(*position, color.opposite())
+                {
+                    return true;
                 }
             }
         }
         false
     }
 
-    pub fn score(&self, color: PieceColor) -> Ic {
-        let mut score = 0;
-        for position in Position::all().iter() {
-            if let Some(piece) = self.piece(*position) {
-                if piece.color() == color {
-                    score += piece.value() as i8;
-                } else {
-                    score -= piece.value() as i8;
-                }
-            }
-        }
-        score
+    pub fn same_placement(&self, other: &Board) -> bool {
+        self.pieces == other.pieces
     }
-}
 
-impl PartialEq for Board {
-    fn eq(&self, other: &Board) -> bool {
-        for position in Position::all().iter() {
-            if self.piece(*position) != other.piece(*position) {
-                return false;
-            }
-        }
-        true
-    }
-    fn ne(&self, other: &Board) -> bool {
-        !self.eq(other)
+    pub fn score(&self, color: PieceColor) -> i32 {
+        let material = self.material();
+        material.value(color) - material.value(color.opposite())
     }
 }
 
-impl fmt::Debug for Board {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f)?;
-        write!(f, " ")?;
-        for col in 0..Position::col_count() {
-            write!(f, " {}", Position::col_char(col))?;
-        }
-        let mut last_row: Option<Uc> = None;
-        for position in Position::all().iter() {
-            let new_row = if let Some(lr) = last_row {
-                if lr != position.row() {
-                    last_row = Some(position.row());
-                    true
-                } else {
-                    false
-                }
-            } else {
-                last_row = Some(position.row());
-                true
-            };
-            if new_row {
-                writeln!(f)?;
-                write!(f, "{}|", Position::row_char(position.row()))?;
-            }
-            if let Some(piece) = self.piece(*position) {
-                write!(f, "{}|", piece.char())?;
-            } else {
-                write!(f, " |")?;
-            }
-        }
-        writeln!(f)
+// Move clocks are left out: they don't change which moves are legal.
+impl PartialEq for Board {
+    fn eq(&self, other: &Board) -> bool {
+        self.pieces == other.pieces
+            && self.turn == other.turn
+            && self.castling_rights() == other.castling_rights()
+            && self.en_passant_target() == other.en_passant_target()
     }
 }
 
@@ -201,8 +244,24 @@ pub struct BoardMove {
     pub to: Position,
 }
 
-impl ToString for BoardMove {
-    fn to_string(&self) -> String {
-        format!("{} -> {}", self.from.to_string(), self.to.to_string(),)
+impl BoardMove {
+    pub fn from_coordinate(coordinate: &str) -> Option<BoardMove> {
+        if coordinate.len() != 4 || !coordinate.is_char_boundary(2) {
+            return None;
+        }
+        Some(BoardMove {
+            from: Position::from_name(&coordinate[0..2])?,
+            to: Position::from_name(&coordinate[2..4])?,
+        })
+    }
+
+    pub fn coordinate(&self) -> String {
+        format!("{}{}", self.from.name(), self.to.name())
+    }
+}
+
+impl fmt::Display for BoardMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.from, self.to)
     }
 }
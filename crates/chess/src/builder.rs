@@ -0,0 +1,343 @@
+use crate::{
+    constants::SQUARE_COUNT, notation, Board, CastlingRights, CastlingSide, Piece, PieceColor,
+    Position,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PositionError {
+    MissingKing(PieceColor),
+    TooManyKings(PieceColor),
+    TooManyPawns(PieceColor),
+    TooManyPieces(PieceColor),
+    PawnOnBackRank(Position),
+    OpponentInCheck(PieceColor),
+    InvalidCastlingRights(PieceColor, CastlingSide),
+    InvalidEnPassant(Position),
+}
+
+impl fmt::Display for PositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::MissingKing(color) => write!(f, "{:?} has no king", color),
+            PositionError::TooManyKings(color) => write!(f, "{:?} has more than one king", color),
+            PositionError::TooManyPawns(color) => {
+                write!(f, "{:?} has more than eight pawns", color)
+            }
+            PositionError::TooManyPieces(color) => {
+                write!(f, "{:?} has more than sixteen pieces", color)
+            }
+            PositionError::PawnOnBackRank(position) => {
+                write!(f, "pawn on back rank at {}", position)
+            }
+            PositionError::OpponentInCheck(color) => {
+                write!(f, "{:?} is in check but it is not their turn", color)
+            }
+            PositionError::InvalidCastlingRights(color, side) => write!(
+                f,
+                "{:?} cannot castle {:?} side without an unmoved king and rook",
+                color, side
+            ),
+            PositionError::InvalidEnPassant(position) => {
+                write!(f, "invalid en passant target {}", position)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PositionError {}
+
+#[derive(Clone, Copy)]
+pub struct BoardBuilder {
+    pieces: [Option<Piece>; SQUARE_COUNT],
+    turn: PieceColor,
+    castling_rights: CastlingRights,
+    en_passant_target: Option<Position>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+}
+
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardBuilder {
+    pub fn new() -> Self {
+        Self {
+            pieces: [None; SQUARE_COUNT],
+            turn: PieceColor::White,
+            castling_rights: CastlingRights::none(),
+            en_passant_target: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    pub fn from_board(board: &Board) -> Self {
+        let mut builder = Self::new();
+        for position in Position::all().iter() {
+            builder.piece(*position, board.piece(*position));
+        }
+        builder
+            .turn(board.turn())
+            .castling_rights(board.castling_rights())
+            .en_passant_target(board.en_passant_target())
+            .halfmove_clock(board.halfmove_clock())
+            .fullmove_number(board.fullmove_number());
+        builder
+    }
+
+    pub fn piece(&mut self, position: Position, piece: Option<Piece>) -> &mut Self {
+        self.pieces[position.index()] = piece;
+        self
+    }
+
+    pub fn turn(&mut self, color: PieceColor) -> &mut Self {
+        self.turn = color;
+        self
+    }
+
+    pub fn castling_rights(&mut self, castling_rights: CastlingRights) -> &mut Self {
+        self.castling_rights = castling_rights;
+        self
+    }
+
+    pub fn en_passant_target(&mut self, target: Option<Position>) -> &mut Self {
+        self.en_passant_target = target;
+        self
+    }
+
+    pub fn halfmove_clock(&mut self, halfmove_clock: u32) -> &mut Self {
+        self.halfmove_clock = halfmove_clock;
+        self
+    }
+
+    pub fn fullmove_number(&mut self, fullmove_number: u32) -> &mut Self {
+        self.fullmove_number = fullmove_number;
+        self
+    }
+
+    pub fn build(&self) -> Result<Board, Vec<PositionError>> {
+        let mut pieces = [None; SQUARE_COUNT];
+        for (square, piece) in pieces.iter_mut().zip(self.pieces.iter()) {
+            *square = piece.map(|piece| piece.fen_char());
+        }
+        let mut castling = [false; 4];
+        for (right, (color, side)) in castling.iter_mut().zip(CASTLING_ORDER) {
+            *right = self.castling_rights.has(color, side);
+        }
+        let errors: Vec<PositionError> = notation::validate(
+            &pieces,
+            self.turn.into(),
+            castling,
+            self.en_passant_target
+                .map(|target| (target.col(), target.row())),
+        )
+        .into_iter()
+        .map(PositionError::from)
+        .collect();
+        if errors.is_empty() {
+            Ok(Board::from_parts(
+                self.pieces,
+                self.turn,
+                self.castling_rights,
+                self.en_passant_target,
+                self.halfmove_clock,
+                self.fullmove_number,
+            ))
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+pub(crate) const CASTLING_ORDER: [(PieceColor, CastlingSide); 4] = [
+    (PieceColor::White, CastlingSide::King),
+    (PieceColor::White, CastlingSide::Queen),
+    (PieceColor::Black, CastlingSide::King),
+    (PieceColor::Black, CastlingSide::Queen),
+];
+
+impl From<PieceColor> for notation::Color {
+    fn from(color: PieceColor) -> Self {
+        match color {
+            PieceColor::White => notation::Color::White,
+            PieceColor::Black => notation::Color::Black,
+        }
+    }
+}
+
+impl From<notation::Color> for PieceColor {
+    fn from(color: notation::Color) -> Self {
+        match color {
+            notation::Color::White => PieceColor::White,
+            notation::Color::Black => PieceColor::Black,
+        }
+    }
+}
+
+impl From<notation::Problem> for PositionError {
+    fn from(problem: notation::Problem) -> Self {
+        let position = |(col, row): notation::Square| Position(col, row);
+        match problem {
+            notation::Problem::MissingKing(color) => PositionError::MissingKing(color.into()),
+            notation::Problem::TooManyKings(color, _) => PositionError::TooManyKings(color.into()),
+            notation::Problem::TooManyPawns(color) => PositionError::TooManyPawns(color.into()),
+            notation::Problem::TooManyPieces(color) => PositionError::TooManyPieces(color.into()),
+            notation::Problem::PawnOnBackRank(square) => {
+                PositionError::PawnOnBackRank(position(square))
+            }
+            notation::Problem::InvalidCastling(right) => {
+                let (color, side) = CASTLING_ORDER[right];
+                PositionError::InvalidCastlingRights(color, side)
+            }
+            notation::Problem::InvalidEnPassant(square) => {
+                PositionError::InvalidEnPassant(position(square))
+            }
+            notation::Problem::OpponentInCheck(color, _) => {
+                PositionError::OpponentInCheck(color.into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoardBuilder, PositionError};
+    use crate::{Board, CastlingRights, CastlingSide, Piece, PieceColor, PieceKind, Position};
+
+    fn kings() -> BoardBuilder {
+        let mut builder = BoardBuilder::new();
+        builder
+            .piece(
+                Position(4, 7),
+                Some(Piece::new(PieceKind::King, PieceColor::White)),
+            )
+            .piece(
+                Position(4, 0),
+                Some(Piece::new(PieceKind::King, PieceColor::Black)),
+            );
+        builder
+    }
+
+    #[test]
+    fn builder_round_trips_new_board() {
+        let board = BoardBuilder::from_board(&Board::new()).build().unwrap();
+        assert_eq!(board, Board::new());
+        assert_eq!(board.turn(), PieceColor::White);
+        assert_eq!(board.castling_rights(), CastlingRights::all());
+        assert_eq!(board.en_passant_target(), None);
+    }
+
+    #[test]
+    fn builder_requires_one_king_each() {
+        assert_eq!(
+            BoardBuilder::new().build().unwrap_err(),
+            vec![
+                PositionError::MissingKing(PieceColor::White),
+                PositionError::MissingKing(PieceColor::Black)
+            ]
+        );
+        let mut builder = kings();
+        builder.piece(
+            Position(0, 4),
+            Some(Piece::new(PieceKind::King, PieceColor::Black)),
+        );
+        assert_eq!(
+            builder.build().unwrap_err(),
+            vec![PositionError::TooManyKings(PieceColor::Black)]
+        );
+    }
+
+    #[test]
+    fn builder_rejects_pawns_on_back_rank() {
+        let mut builder = kings();
+        builder.piece(
+            Position(0, 7),
+            Some(Piece::new(PieceKind::Pawn, PieceColor::White)),
+        );
+        assert_eq!(
+            builder.build().unwrap_err(),
+            vec![PositionError::PawnOnBackRank(Position(0, 7))]
+        );
+    }
+
+    #[test]
+    fn builder_rejects_opponent_in_check() {
+        let mut builder = kings();
+        builder
+            .piece(
+                Position(4, 4),
+                Some(Piece::new(PieceKind::Rook, PieceColor::White)),
+            )
+            .turn(PieceColor::Black);
+        assert!(builder.build().unwrap().check(PieceColor::Black));
+        builder.turn(PieceColor::White);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            vec![PositionError::OpponentInCheck(PieceColor::Black)]
+        );
+    }
+
+    #[test]
+    fn builder_validates_castling_rights() {
+        let mut builder = kings();
+        builder
+            .piece(
+                Position(7, 7),
+                Some(Piece::new(PieceKind::Rook, PieceColor::White)),
+            )
+            .castling_rights(CastlingRights::all());
+        assert_eq!(
+            builder.build().unwrap_err(),
+            vec![
+                PositionError::InvalidCastlingRights(PieceColor::White, CastlingSide::Queen),
+                PositionError::InvalidCastlingRights(PieceColor::Black, CastlingSide::King),
+                PositionError::InvalidCastlingRights(PieceColor::Black, CastlingSide::Queen),
+            ]
+        );
+        let mut rights = CastlingRights::none();
+        rights.set(PieceColor::White, CastlingSide::King, true);
+        let board = builder.castling_rights(rights).build().unwrap();
+        assert_eq!(board.castling_rights(), rights);
+        assert!(board.is_valid_move(crate::board_move!(e1 g1)));
+    }
+
+    #[test]
+    fn builder_unmoved_pieces_need_castling_rights() {
+        let mut builder = kings();
+        builder.piece(
+            Position(7, 7),
+            Some(Piece::new(PieceKind::Rook, PieceColor::White)),
+        );
+        let board = builder.build().unwrap();
+        assert!(!board.is_valid_move(crate::board_move!(e1 g1)));
+    }
+
+    #[test]
+    fn builder_validates_en_passant_target() {
+        let mut builder = kings();
+        builder
+            .piece(
+                Position(3, 3),
+                Some(Piece::new(PieceKind::Pawn, PieceColor::Black)),
+            )
+            .piece(
+                Position(4, 3),
+                Some(Piece::new(PieceKind::Pawn, PieceColor::White)),
+            )
+            .en_passant_target(Some(Position(3, 2)));
+        let board = builder.build().unwrap();
+        assert_eq!(board.en_passant_target(), Some(Position(3, 2)));
+        assert!(board.is_valid_move(crate::board_move!(e5 d6)));
+        builder.en_passant_target(Some(Position(4, 2)));
+        assert_eq!(
+            builder.build().unwrap_err(),
+            vec![PositionError::InvalidEnPassant(Position(4, 2))]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use crate::{constants::SQUARE_COUNT, Board, BoardMove, Piece, PieceColor, PieceKind, Position};
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Glyphs {
+    #[default]
+    Ascii,
+    Unicode,
+}
+
+impl Glyphs {
+    pub fn piece(&self, piece: &Piece) -> char {
+        match self {
+            Glyphs::Ascii => piece.fen_char(),
+            Glyphs::Unicode => match piece.color() {
+                PieceColor::White => match piece.kind() {
+                    PieceKind::Pawn => '♙',
+                    PieceKind::Rook => '♖',
+                    PieceKind::Knight => '♘',
+                    PieceKind::Bishop => '♗',
+                    PieceKind::Queen => '♕',
+                    PieceKind::King => '♔',
+                },
+                PieceColor::Black => match piece.kind() {
+                    PieceKind::Pawn => '♟',
+                    PieceKind::Rook => '♜',
+                    PieceKind::Knight => '♞',
+                    PieceKind::Bishop => '♝',
+                    PieceKind::Queen => '♛',
+                    PieceKind::King => '♚',
+                },
+            },
+        }
+    }
+
+    pub fn empty(&self) -> char {
+        match self {
+            Glyphs::Ascii => '.',
+            Glyphs::Unicode => '·',
+        }
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_LIGHT_SQUARE: &str = "\x1b[48;5;252m";
+const ANSI_DARK_SQUARE: &str = "\x1b[48;5;67m";
+const ANSI_LIGHT_HIGHLIGHT: &str = "\x1b[48;5;186m";
+const ANSI_DARK_HIGHLIGHT: &str = "\x1b[48;5;143m";
+const ANSI_WHITE_PIECE: &str = "\x1b[1;97m";
+const ANSI_BLACK_PIECE: &str = "\x1b[1;30m";
+
+#[derive(Clone, Copy)]
+pub struct BoardDisplay<'a> {
+    board: &'a Board,
+    glyphs: Glyphs,
+    orientation: PieceColor,
+    labels: bool,
+    colors: bool,
+    highlights: [bool; SQUARE_COUNT],
+}
+
+impl<'a> BoardDisplay<'a> {
+    pub fn new(board: &'a Board) -> Self {
+        Self {
+            board,
+            glyphs: Glyphs::Ascii,
+            orientation: PieceColor::White,
+            labels: true,
+            colors: false,
+            highlights: [false; SQUARE_COUNT],
+        }
+    }
+
+    pub fn glyphs(mut self, glyphs: Glyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: PieceColor) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    pub fn highlight(mut self, position: Position) -> Self {
+        self.highlights[position.index()] = true;
+        self
+    }
+
+    pub fn highlight_move(self, board_move: BoardMove) -> Self {
+        self.highlight(board_move.from).highlight(board_move.to)
+    }
+
+    pub fn highlight_targets(mut self, moves: impl IntoIterator<Item = BoardMove>) -> Self {
+        for board_move in moves {
+            self = self.highlight(board_move.to);
+        }
+        self
+    }
+
+    fn cols(&self) -> impl Iterator<Item = u8> {
+        let flip = self.orientation == PieceColor::Black;
+        (0..Position::col_count()).map(move |col| {
+            if flip {
+                Position::col_count() - col - 1
+            } else {
+                col
+            }
+        })
+    }
+
+    fn rows(&self) -> impl Iterator<Item = u8> {
+        let flip = self.orientation == PieceColor::Black;
+        (0..Position::row_count()).map(move |row| {
+            if flip {
+                Position::row_count() - row - 1
+            } else {
+                row
+            }
+        })
+    }
+
+    fn write_files(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, " ")?;
+        for col in self.cols() {
+            write!(f, " {} ", Position::col_char(col).to_ascii_lowercase())?;
+        }
+        writeln!(f)
+    }
+
+    fn write_square(&self, f: &mut fmt::Formatter<'_>, position: Position) -> fmt::Result {
+        let piece = self.board.piece(position);
+        let glyph = piece.map_or(self.glyphs.empty(), |piece| self.glyphs.piece(&piece));
+        let highlighted = self.highlights[position.index()];
+        if self.colors {
+            let light = position.is_light_square();
+            let background = match (light, highlighted) {
+                (true, false) => ANSI_LIGHT_SQUARE,
+                (false, false) => ANSI_DARK_SQUARE,
+                (true, true) => ANSI_LIGHT_HIGHLIGHT,
+                (false, true) => ANSI_DARK_HIGHLIGHT,
+            };
+            let foreground = match piece.map(|piece| piece.color()) {
+                Some(PieceColor::White) => ANSI_WHITE_PIECE,
+                _ => ANSI_BLACK_PIECE,
+            };
+            write!(f, "{}{} {} {}", background, foreground, glyph, ANSI_RESET)
+        } else if highlighted {
+            write!(f, "[{}]", glyph)
+        } else {
+            write!(f, " {} ", glyph)
+        }
+    }
+}
+
+impl<'a> fmt::Display for BoardDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.labels {
+            self.write_files(f)?;
+        }
+        for row in self.rows() {
+            if self.labels {
+                write!(f, "{}", Position::row_char(row))?;
+            }
+            for col in self.cols() {
+                self.write_square(f, Position(col, row))?;
+            }
+            if self.labels {
+                write!(f, "{}", Position::row_char(row))?;
+            }
+            writeln!(f)?;
+        }
+        if self.labels {
+            self.write_files(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Board {
+    pub fn display(&self) -> BoardDisplay<'_> {
+        BoardDisplay::new(self)
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display())
+    }
+}
+
+impl fmt::Debug for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f)?;
+        write!(f, "{}", self.display())?;
+        writeln!(f, "{}", self.to_fen())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Glyphs;
+    use crate::{Board, PieceColor, Position};
+    use chess_macros::board_move;
+
+    #[test]
+    fn display_ascii() {
+        assert_eq!(
+            Board::new().to_string(),
+            concat!(
+                "  a  b  c  d  e  f  g  h \n",
+                "8 r  n  b  q  k  b  n  r 8\n",
+                "7 p  p  p  p  p  p  p  p 7\n",
+                "6 .  .  .  .  .  .  .  . 6\n",
+                "5 .  .  .  .  .  .  .  . 5\n",
+                "4 .  .  .  .  .  .  .  . 4\n",
+                "3 .  .  .  .  .  .  .  . 3\n",
+                "2 P  P  P  P  P  P  P  P 2\n",
+                "1 R  N  B  Q  K  B  N  R 1\n",
+                "  a  b  c  d  e  f  g  h \n",
+            )
+        );
+    }
+
+    #[test]
+    fn display_unicode_black_orientation() {
+        let board = Board::new();
+        let display = board
+            .display()
+            .glyphs(Glyphs::Unicode)
+            .orientation(PieceColor::Black)
+            .labels(false)
+            .to_string();
+        let lines: Vec<&str> = display.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], " ♖  ♘  ♗  ♔  ♕  ♗  ♘  ♖ ");
+        assert_eq!(lines[7], " ♜  ♞  ♝  ♚  ♛  ♝  ♞  ♜ ");
+    }
+
+    #[test]
+    fn display_highlights() {
+        let board = Board::new();
+        let display = board
+            .display()
+            .labels(false)
+            .highlight_move(board_move!(e2 e4))
+            .to_string();
+        let lines: Vec<&str> = display.lines().collect();
+        assert_eq!(lines[4], " .  .  .  . [.] .  .  . ");
+        assert_eq!(lines[6], " P  P  P  P [P] P  P  P ");
+        let targets = board
+            .display()
+            .labels(false)
+            .highlight_targets(board.moves_from(Position(1, 7)))
+            .to_string();
+        assert_eq!(targets.lines().nth(5).unwrap(), "[.] . [.] .  .  .  .  . ");
+    }
+
+    #[test]
+    fn display_colors() {
+        let board = Board::new();
+        let display = board.display().colors(true).labels(false).to_string();
+        assert!(display.starts_with("\x1b[48;5;252m\x1b[1;30m r \x1b[0m"));
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{Board, BoardBuilder, BoardMove, FenError};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EpdError {
+    Fen(FenError),
+    InvalidOperation(String),
+    InvalidMove(String),
+}
+
+impl fmt::Display for EpdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EpdError::Fen(error) => write!(f, "{}", error),
+            EpdError::InvalidOperation(operation) => {
+                write!(f, "invalid EPD operation `{}`", operation)
+            }
+            EpdError::InvalidMove(san) => write!(f, "invalid EPD move `{}`", san),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EpdError {}
+
+impl From<FenError> for EpdError {
+    fn from(error: FenError) -> Self {
+        EpdError::Fen(error)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpdOperation {
+    pub opcode: String,
+    pub operands: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Epd {
+    board: Board,
+    operations: Vec<EpdOperation>,
+}
+
+impl Epd {
+    pub fn parse(epd: &str) -> Result<Epd, EpdError> {
+        let mut rest = epd.trim();
+        let mut fields = Vec::new();
+        for _ in 0..4 {
+            rest = rest.trim_start();
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            fields.push(&rest[..end]);
+            rest = &rest[end..];
+        }
+        let mut builder = BoardBuilder::from_fen(&fields.join(" "))?;
+        let operations = parse_operations(rest)?;
+        for operation in operations.iter() {
+            let number = || -> Result<u32, EpdError> {
+                match operation.operands.as_slice() {
+                    [number] => number
+                        .parse()
+                        .map_err(|_| EpdError::InvalidOperation(operation.to_string())),
+                    _ => Err(EpdError::InvalidOperation(operation.to_string())),
+                }
+            };
+            match operation.opcode.as_str() {
+                "hmvc" => {
+                    builder.halfmove_clock(number()?);
+                }
+                "fmvn" => {
+                    builder.fullmove_number(number()?);
+                }
+                _ => {}
+            }
+        }
+        let board = builder
+            .build()
+            .map_err(|errors| EpdError::Fen(FenError::InvalidPosition(errors)))?;
+        Ok(Epd { board, operations })
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn operations(&self) -> &[EpdOperation] {
+        &self.operations
+    }
+
+    pub fn operation(&self, opcode: &str) -> Option<&EpdOperation> {
+        self.operations
+            .iter()
+            .find(|operation| operation.opcode == opcode)
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.operation("id")
+            .and_then(|operation| operation.operands.first())
+            .map(|id| id.as_str())
+    }
+
+    pub fn best_moves(&self) -> Result<Vec<BoardMove>, EpdError> {
+        self.moves("bm")
+    }
+
+    pub fn avoid_moves(&self) -> Result<Vec<BoardMove>, EpdError> {
+        self.moves("am")
+    }
+
+    fn moves(&self, opcode: &str) -> Result<Vec<BoardMove>, EpdError> {
+        self.operation(opcode)
+            .map_or(&[][..], |operation| operation.operands.as_slice())
+            .iter()
+            .map(|san| {
+                self.board
+                    .parse_san(san)
+                    .ok_or_else(|| EpdError::InvalidMove(san.to_owned()))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for EpdOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.opcode)?;
+        for operand in self.operands.iter() {
+            if operand.is_empty() || operand.contains(char::is_whitespace) || operand.contains(';')
+            {
+                write!(f, " \"{}\"", operand)?;
+            } else {
+                write!(f, " {}", operand)?;
+            }
+        }
+        write!(f, ";")
+    }
+}
+
+fn parse_operations(operations: &str) -> Result<Vec<EpdOperation>, EpdError> {
+    let mut parsed = Vec::new();
+    let mut chars = operations.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(parsed);
+        }
+        let mut opcode = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ';') {
+            opcode.push(c);
+        }
+        if !opcode.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return Err(EpdError::InvalidOperation(opcode));
+        }
+        let mut operands = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            match chars.next() {
+                None | Some(';') => break,
+                Some('"') => {
+                    let mut operand = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(c) => operand.push(c),
+                            None => return Err(EpdError::InvalidOperation(opcode)),
+                        }
+                    }
+                    operands.push(operand);
+                }
+                Some(c) => {
+                    let mut operand = String::from(c);
+                    while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ';') {
+                        operand.push(c);
+                    }
+                    operands.push(operand);
+                }
+            }
+        }
+        parsed.push(EpdOperation { opcode, operands });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Epd, EpdError, EpdOperation};
+    use crate::FenError;
+    use chess_macros::board_move;
+
+    #[test]
+    fn epd_parses_operations() {
+        let epd = Epd::parse(
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\"; c0 \"mate; in two\";",
+        )
+        .unwrap();
+        assert_eq!(epd.id(), Some("WAC.001"));
+        assert_eq!(epd.best_moves(), Ok(vec![board_move!(g3 g6)]));
+        assert_eq!(epd.avoid_moves(), Ok(vec![]));
+        assert_eq!(
+            epd.operation("c0"),
+            Some(&EpdOperation {
+                opcode: "c0".to_owned(),
+                operands: vec!["mate; in two".to_owned()],
+            })
+        );
+        assert_eq!(
+            epd.operation("c0").unwrap().to_string(),
+            "c0 \"mate; in two\";"
+        );
+        assert_eq!(
+            epd.board().to_fen(),
+            "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1"
+        );
+    }
+
+    #[test]
+    fn epd_move_counters_and_multiple_moves() {
+        let epd = Epd::parse("4k3/8/8/8/8/8/8/R3K2R w KQ - bm O-O Rd1; am Kf2; hmvc 4; fmvn 30;")
+            .unwrap();
+        assert_eq!(
+            epd.best_moves(),
+            Ok(vec![board_move!(e1 g1), board_move!(a1 d1)])
+        );
+        assert_eq!(epd.avoid_moves(), Ok(vec![board_move!(e1 f2)]));
+        assert_eq!(epd.board().halfmove_clock(), 4);
+        assert_eq!(epd.board().fullmove_number(), 30);
+    }
+
+    #[test]
+    fn epd_errors() {
+        assert_eq!(
+            Epd::parse("4k3/8/8/8/8/8/8/4K3 w -").unwrap_err(),
+            EpdError::Fen(FenError::MissingField("en passant"))
+        );
+        assert_eq!(
+            Epd::parse("4k3/8/8/8/8/8/8/4K3 w - - 1bm e4;").unwrap_err(),
+            EpdError::InvalidOperation("1bm".to_owned())
+        );
+        assert_eq!(
+            Epd::parse("4k3/8/8/8/8/8/8/4K3 w - - id \"open;").unwrap_err(),
+            EpdError::InvalidOperation("id".to_owned())
+        );
+        let epd = Epd::parse("4k3/8/8/8/8/8/8/4K3 w - - bm Qh5;").unwrap();
+        assert_eq!(
+            epd.best_moves(),
+            Err(EpdError::InvalidMove("Qh5".to_owned()))
+        );
+    }
+}
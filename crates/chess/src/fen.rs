@@ -0,0 +1,302 @@
+use crate::{
+    builder::CASTLING_ORDER, notation, Board, BoardBuilder, CastlingRights, Piece, PieceColor,
+    Position, PositionError,
+};
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FenError {
+    MissingField(&'static str),
+    TooManyFields,
+    InvalidPlacement(String),
+    InvalidTurn(String),
+    InvalidCastling(String),
+    InvalidEnPassant(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+    InvalidPosition(Vec<PositionError>),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::MissingField(field) => write!(f, "missing FEN field: {}", field),
+            FenError::TooManyFields => write!(f, "too many FEN fields"),
+            FenError::InvalidPlacement(field) => write!(f, "invalid piece placement `{}`", field),
+            FenError::InvalidTurn(field) => write!(f, "invalid side to move `{}`", field),
+            FenError::InvalidCastling(field) => write!(f, "invalid castling rights `{}`", field),
+            FenError::InvalidEnPassant(field) => write!(f, "invalid en passant target `{}`", field),
+            FenError::InvalidHalfmoveClock(field) => {
+                write!(f, "invalid halfmove clock `{}`", field)
+            }
+            FenError::InvalidFullmoveNumber(field) => {
+                write!(f, "invalid fullmove number `{}`", field)
+            }
+            FenError::InvalidPosition(errors) => {
+                write!(f, "invalid position:")?;
+                for error in errors.iter() {
+                    write!(f, " {};", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FenError {}
+
+impl Board {
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        BoardBuilder::from_fen(fen)?
+            .build()
+            .map_err(FenError::InvalidPosition)
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+        for row in 0..Position::row_count() {
+            if row > 0 {
+                fen.push('/');
+            }
+            let mut empty = 0;
+            for col in 0..Position::col_count() {
+                if let Some(piece) = self.piece(Position(col, row)) {
+                    if empty > 0 {
+                        fen.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    fen.push(piece.fen_char());
+                } else {
+                    empty += 1;
+                }
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+        }
+        fen.push(' ');
+        fen.push(match self.turn() {
+            PieceColor::White => 'w',
+            PieceColor::Black => 'b',
+        });
+        fen.push(' ');
+        fen.push_str(&castling_to_fen(self.castling_rights()));
+        fen.push(' ');
+        if let Some(target) = self.en_passant_target() {
+            fen.push_str(&target.name());
+        } else {
+            fen.push('-');
+        }
+        fen.push_str(&format!(
+            " {} {}",
+            self.halfmove_clock(),
+            self.fullmove_number()
+        ));
+        fen
+    }
+}
+
+impl BoardBuilder {
+    pub fn from_fen(fen: &str) -> Result<BoardBuilder, FenError> {
+        let mut fields = fen.split_whitespace();
+        let placement = fields.next().ok_or(FenError::MissingField("placement"))?;
+        let turn = fields.next().ok_or(FenError::MissingField("turn"))?;
+        let castling = fields.next().ok_or(FenError::MissingField("castling"))?;
+        let en_passant = fields.next().ok_or(FenError::MissingField("en passant"))?;
+        let halfmove_clock = fields.next().unwrap_or("0");
+        let fullmove_number = fields.next().unwrap_or("1");
+        if fields.next().is_some() {
+            return Err(FenError::TooManyFields);
+        }
+
+        let mut builder = BoardBuilder::new();
+        let pieces = notation::parse_placement(placement)
+            .map_err(|_| FenError::InvalidPlacement(placement.to_owned()))?;
+        for position in Position::all().iter() {
+            builder.piece(
+                *position,
+                pieces[position.index()].and_then(Piece::from_fen_char),
+            );
+        }
+
+        builder.turn(match turn {
+            "w" => PieceColor::White,
+            "b" => PieceColor::Black,
+            _ => return Err(FenError::InvalidTurn(turn.to_owned())),
+        });
+        builder.castling_rights(
+            castling_from_fen(castling)
+                .ok_or_else(|| FenError::InvalidCastling(castling.to_owned()))?,
+        );
+        builder.en_passant_target(if en_passant == "-" {
+            None
+        } else {
+            Some(
+                Position::from_name(en_passant)
+                    .ok_or_else(|| FenError::InvalidEnPassant(en_passant.to_owned()))?,
+            )
+        });
+        builder.halfmove_clock(
+            halfmove_clock
+                .parse()
+                .map_err(|_| FenError::InvalidHalfmoveClock(halfmove_clock.to_owned()))?,
+        );
+        builder.fullmove_number(
+            fullmove_number
+                .parse()
+                .ok()
+                .filter(|number| *number > 0)
+                .ok_or_else(|| FenError::InvalidFullmoveNumber(fullmove_number.to_owned()))?,
+        );
+        Ok(builder)
+    }
+}
+
+fn castling_to_fen(rights: CastlingRights) -> String {
+    if rights.is_empty() {
+        return "-".to_owned();
+    }
+    notation::CASTLING_CHARS
+        .iter()
+        .zip(CASTLING_ORDER)
+        .filter(|(_, (color, side))| rights.has(*color, *side))
+        .map(|(c, _)| *c)
+        .collect()
+}
+
+fn castling_from_fen(castling: &str) -> Option<CastlingRights> {
+    let mut rights = CastlingRights::none();
+    for (has, (color, side)) in notation::parse_castling(castling)?
+        .iter()
+        .zip(CASTLING_ORDER)
+    {
+        rights.set(color, side, *has);
+    }
+    Some(rights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FenError, STARTING_FEN};
+    use crate::{Board, PieceColor, Position, PositionError};
+    use chess_macros::{board, board_move, fen};
+
+    #[test]
+    fn fen_starting_position() {
+        let board = Board::from_fen(STARTING_FEN).unwrap();
+        assert_eq!(board, Board::new());
+        assert_eq!(Board::new().to_fen(), STARTING_FEN);
+    }
+
+    #[test]
+    fn fen_tracks_moves() {
+        let mut board = Board::new();
+        assert!(board.apply_move(board_move!(e2 e4)));
+        assert_eq!(
+            board.to_fen(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+        assert!(board.apply_move(board_move!(g8 f6)));
+        assert_eq!(
+            board.to_fen(),
+            "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2"
+        );
+        assert!(board.apply_move(board_move!(e1 e2)));
+        assert_eq!(
+            board.to_fen(),
+            "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPPKPPP/RNBQ1BNR b kq - 2 2"
+        );
+    }
+
+    #[test]
+    fn fen_round_trip() {
+        let fen = "r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 0 24";
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(board.turn(), PieceColor::White);
+        assert_eq!(board.en_passant_target(), Some(Position(3, 2)));
+        assert!(board.is_valid_move(board_move!(e5 d6)));
+        assert!(board.is_valid_move(board_move!(e1 g1)));
+        assert!(!board.is_valid_move(board_move!(e1 c1)));
+    }
+
+    #[test]
+    fn fen_errors() {
+        assert_eq!(
+            Board::from_fen("8/8/8/8/8/8/8 w - -"),
+            Err(FenError::InvalidPlacement("8/8/8/8/8/8/8".to_owned()))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 x - -"),
+            Err(FenError::InvalidTurn("x".to_owned()))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 w KK -"),
+            Err(FenError::InvalidCastling("KK".to_owned()))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 w -"),
+            Err(FenError::MissingField("en passant"))
+        );
+        assert_eq!(
+            Board::from_fen("4k3/8/8/8/8/8/8/8 w - -"),
+            Err(FenError::InvalidPosition(vec![PositionError::MissingKing(
+                PieceColor::White
+            )]))
+        );
+    }
+
+    #[test]
+    fn fen_macro_matches_runtime_parser() {
+        let fen = "r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 3 24";
+        let board = fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 3 24");
+        assert_eq!(board, Board::from_fen(fen).unwrap());
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(
+            fen!("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -"),
+            Board::new()
+        );
+        assert_ne!(board, fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R b Kq - 3 24"));
+        assert_ne!(board, fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 3 24"));
+        assert_ne!(board, fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq - 3 24"));
+        assert_eq!(board, fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 0 1"));
+    }
+
+    #[test]
+    fn board_macro_clauses() {
+        let board = board!(
+            R _ _ _ K _ _ R
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ P p _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            r _ _ _ k _ _ r,
+            castling: Kq,
+            ep: d6
+        );
+        assert_eq!(board.to_fen(), "r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 0 1");
+        let board = board!(
+            _ _ _ _ K _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ k _ _ _
+            black_to_move castling: -
+        );
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K3 b - - 0 1");
+    }
+}
@@ -1,13 +1,42 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub type Uc = u8;
 pub type Ic = i8;
 
+pub use attacks::{BISHOP_DIRECTIONS, KING_OFFSETS, KNIGHT_OFFSETS, ROOK_DIRECTIONS};
 pub use board::{Board, BoardMove};
-pub use chess_macros::{board, board_move};
+pub use builder::{BoardBuilder, PositionError};
+pub use chess_macros::{board, board_move, fen};
+pub use constants::SQUARE_COUNT;
+pub use display::{BoardDisplay, Glyphs};
+pub use epd::{Epd, EpdError, EpdOperation};
+pub use fen::{FenError, STARTING_FEN};
+pub use material::Material;
+pub use moves::ValidMoves;
 pub use piece::{Piece, PieceColor, PieceKind};
 pub use position::{Position, PositionOffset};
+pub use rules::castle::{CastlingRights, CastlingSide};
 
+mod attacks;
 mod board;
+mod builder;
 mod constants;
+mod display;
+mod epd;
+mod fen;
+mod material;
+mod moves;
+mod notation;
+mod offsets;
 mod piece;
 mod position;
+#[cfg(feature = "random")]
+pub mod random;
 mod rules;
+mod san;
+#[cfg(feature = "serde")]
+mod serialization;
+mod transform;
+mod zobrist;
@@ -0,0 +1,156 @@
+use crate::{Board, PieceColor, PieceKind, Position};
+use alloc::string::String;
+
+const SIGNATURE_ORDER: [(PieceKind, char); 6] = [
+    (PieceKind::King, 'K'),
+    (PieceKind::Queen, 'Q'),
+    (PieceKind::Rook, 'R'),
+    (PieceKind::Bishop, 'B'),
+    (PieceKind::Knight, 'N'),
+    (PieceKind::Pawn, 'P'),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct Material {
+    counts: [[u8; PieceKind::ALL.len()]; 2],
+}
+
+impl Material {
+    fn color_index(color: PieceColor) -> usize {
+        match color {
+            PieceColor::White => 0,
+            PieceColor::Black => 1,
+        }
+    }
+
+    pub fn count(&self, color: PieceColor, kind: PieceKind) -> u8 {
+        self.counts[Self::color_index(color)][kind.index()]
+    }
+
+    pub fn total(&self, color: PieceColor) -> u8 {
+        self.counts[Self::color_index(color)].iter().sum()
+    }
+
+    pub fn value(&self, color: PieceColor) -> i32 {
+        PieceKind::ALL
+            .iter()
+            .filter(|kind| **kind != PieceKind::King)
+            .map(|kind| {
+                crate::Piece::new(*kind, color).value() as i32 * self.count(color, *kind) as i32
+            })
+            .sum()
+    }
+
+    pub fn from_signature(signature: &str) -> Option<Material> {
+        let (white, black) = signature.split_once('v')?;
+        let mut material = Material::default();
+        for (color, pieces) in [(PieceColor::White, white), (PieceColor::Black, black)] {
+            for c in pieces.chars() {
+                let (kind, _) = SIGNATURE_ORDER.iter().find(|(_, kind_c)| *kind_c == c)?;
+                material.counts[Self::color_index(color)][kind.index()] += 1;
+            }
+        }
+        Some(material)
+    }
+
+    pub fn signature(&self) -> String {
+        let mut signature = String::new();
+        for color in [PieceColor::White, PieceColor::Black] {
+            if color == PieceColor::Black {
+                signature.push('v');
+            }
+            for (kind, c) in SIGNATURE_ORDER.iter() {
+                for _ in 0..self.count(color, *kind) {
+                    signature.push(*c);
+                }
+            }
+        }
+        signature
+    }
+}
+
+impl Board {
+    pub fn material(&self) -> Material {
+        let mut material = Material::default();
+        for position in Position::all().iter() {
+            if let Some(piece) = self.piece(*position) {
+                material.counts[Material::color_index(piece.color())][piece.kind().index()] += 1;
+            }
+        }
+        material
+    }
+
+    pub fn is_insufficient_material(&self) -> bool {
+        let mut knights = 0;
+        let mut light_bishops = 0;
+        let mut dark_bishops = 0;
+        for position in Position::all().iter() {
+            if let Some(piece) = self.piece(*position) {
+                match piece.kind() {
+                    PieceKind::King => {}
+                    PieceKind::Knight => knights += 1,
+                    PieceKind::Bishop => {
+                        if position.is_light_square() {
+                            light_bishops += 1;
+                        } else {
+                            dark_bishops += 1;
+                        }
+                    }
+                    PieceKind::Pawn | PieceKind::Rook | PieceKind::Queen => return false,
+                }
+            }
+        }
+        knights + light_bishops + dark_bishops <= 1
+            || (knights == 0 && (light_bishops == 0 || dark_bishops == 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Material, PieceColor, PieceKind};
+    use chess_macros::{board, fen};
+
+    #[test]
+    fn material_counts_and_signature() {
+        let material = Board::new().material();
+        assert_eq!(material.count(PieceColor::White, PieceKind::Pawn), 8);
+        assert_eq!(material.count(PieceColor::Black, PieceKind::Queen), 1);
+        assert_eq!(material.total(PieceColor::Black), 16);
+        assert_eq!(material.value(PieceColor::White), 38);
+        assert_eq!(material.signature(), "KQRRBBNNPPPPPPPPvKQRRBBNNPPPPPPPP");
+        let material = fen!("8/4k3/4r3/8/8/4P3/4R3/4K3 w - - 0 1").material();
+        assert_eq!(material.signature(), "KRPvKR");
+        assert_eq!(Material::from_signature("KPRvRK"), Some(material));
+        assert_eq!(Material::from_signature("KRP"), None);
+        assert_eq!(Material::from_signature("KXvK"), None);
+    }
+
+    #[test]
+    fn score_covers_the_full_material_difference() {
+        let board = board!(
+            q q q q q q q q
+            q q q q q q q q
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+        );
+        assert_eq!(board.material().value(PieceColor::White), 128);
+        assert_eq!(board.score(PieceColor::White), 128);
+        assert_eq!(board.score(PieceColor::Black), -128);
+    }
+
+    #[test]
+    fn insufficient_material() {
+        assert!(fen!("8/8/4k3/8/8/4K3/8/8 w - - 0 1").is_insufficient_material());
+        assert!(fen!("8/8/4k3/8/8/4KB2/8/8 w - - 0 1").is_insufficient_material());
+        assert!(fen!("8/8/4k3/8/8/4KN2/8/8 w - - 0 1").is_insufficient_material());
+        assert!(fen!("8/8/2b1k3/8/8/4KB2/8/8 w - - 0 1").is_insufficient_material());
+        assert!(!fen!("8/8/3bk3/8/8/4KB2/8/8 w - - 0 1").is_insufficient_material());
+        assert!(!fen!("8/8/4k3/8/8/4KNN1/8/8 w - - 0 1").is_insufficient_material());
+        assert!(!fen!("8/8/4k3/8/8/4K3/4P3/8 w - - 0 1").is_insufficient_material());
+        assert!(!Board::new().is_insufficient_material());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{constants::SQUARE_COUNT, Board, BoardMove, PieceColor, Position};
+use core::ops::Range;
+
+pub struct ValidMoves<'a> {
+    board: &'a Board,
+    color: Option<PieceColor>,
+    to: Option<Position>,
+    squares: Range<usize>,
+    planned: [Option<BoardMove>; SQUARE_COUNT],
+    planned_squares: Range<usize>,
+}
+
+impl<'a> ValidMoves<'a> {
+    pub(crate) fn new(
+        board: &'a Board,
+        squares: Range<usize>,
+        color: Option<PieceColor>,
+        to: Option<Position>,
+    ) -> Self {
+        Self {
+            board,
+            color,
+            to,
+            squares,
+            planned: [None; SQUARE_COUNT],
+            planned_squares: 0..0,
+        }
+    }
+}
+
+impl<'a> Iterator for ValidMoves<'a> {
+    type Item = BoardMove;
+
+    fn next(&mut self) -> Option<BoardMove> {
+        loop {
+            for index in self.planned_squares.by_ref() {
+                if let Some(board_move) = self.planned[index] {
+                    if self.board.is_legal(board_move) {
+                        return Some(board_move);
+                    }
+                }
+            }
+            let from = Position::all()[self.squares.next()?];
+            if let Some(piece) = self.board.piece(from) {
+                if self.color.map_or(true, |color| color == piece.color()) {
+                    self.planned = self.board.plan_moves(from, &piece);
+                    self.planned_squares = match self.to {
+                        Some(to) => to.index()..to.index() + 1,
+                        None => 0..SQUARE_COUNT,
+                    };
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PieceColor, Position};
+    use chess_macros::{board, board_move};
+
+    #[test]
+    fn valid_moves_from_single_piece() {
+        let board = board!(
+            _ _ _ _ K _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            p _ _ _ _ _ _ _
+            n _ _ _ k _ _ _
+        );
+        assert_eq!(
+            board.valid_moves_from(Position(0, 7)),
+            vec![board_move!(a1 b3), board_move!(a1 c2)]
+        );
+        assert_eq!(
+            board.valid_moves_from(Position(0, 6)),
+            vec![board_move!(a2 a4), board_move!(a2 a3)]
+        );
+        assert!(board.valid_moves_from(Position(3, 3)).is_empty());
+    }
+
+    #[test]
+    fn valid_moves_from_respects_pins() {
+        let board = board!(
+            _ _ _ _ R _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ n _ _ _
+            _ _ _ _ k _ _ _
+        );
+        assert!(board.valid_moves_from(Position(4, 6)).is_empty());
+    }
+
+    #[test]
+    fn valid_moves_to_square() {
+        let board = board!(
+            _ _ _ _ K _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ p _ _ _ _ _
+            _ n _ _ k _ _ _
+        );
+        assert_eq!(
+            board.valid_moves_to(Position(2, 5)),
+            vec![board_move!(c2 c3), board_move!(b1 c3)]
+        );
+        assert_eq!(board.moves_to(Position(3, 7)).count(), 1);
+    }
+
+    #[test]
+    fn moves_matches_valid_moves() {
+        let board = crate::Board::new();
+        assert_eq!(board.moves(PieceColor::White).count(), 20);
+        assert_eq!(
+            board.moves(PieceColor::Black).collect::<Vec<_>>(),
+            board.valid_moves(PieceColor::Black)
+        );
+    }
+}
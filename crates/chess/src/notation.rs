@@ -0,0 +1,328 @@
+// Shared with chess_macros, which includes this file with `#[path]` so that
+// `fen!`/`board!` reject exactly the positions `BoardBuilder` rejects. It may
+// only depend on `core`, `alloc` and the shared `offsets` tables.
+
+use crate::offsets;
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+pub(crate) const SIZE: u8 = 8;
+const COL_CHARS: [char; 8] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+const ROW_CHARS: [char; 8] = ['8', '7', '6', '5', '4', '3', '2', '1'];
+pub(crate) const CASTLING_CHARS: [char; 4] = ['K', 'Q', 'k', 'q'];
+
+pub(crate) type Square = (u8, u8);
+pub(crate) type Placement = [Option<char>; 64];
+pub(crate) type Castling = [bool; 4];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    pub(crate) fn opposite(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+
+    fn of(piece: char) -> Color {
+        if piece.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        }
+    }
+
+    fn piece(self, kind: char) -> char {
+        match self {
+            Color::White => kind.to_ascii_uppercase(),
+            Color::Black => kind.to_ascii_lowercase(),
+        }
+    }
+
+    fn forward(self) -> i8 {
+        match self {
+            Color::White => -1,
+            Color::Black => 1,
+        }
+    }
+
+    fn pawn_row(self) -> u8 {
+        match self {
+            Color::White => 6,
+            Color::Black => 1,
+        }
+    }
+
+    fn back_row(self) -> u8 {
+        match self {
+            Color::White => 7,
+            Color::Black => 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum PlacementError {
+    RankCount(usize),
+    InvalidPiece(Range<usize>),
+    InvalidRank(Range<usize>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Problem {
+    MissingKing(Color),
+    TooManyKings(Color, Square),
+    TooManyPawns(Color),
+    TooManyPieces(Color),
+    PawnOnBackRank(Square),
+    InvalidCastling(usize),
+    InvalidEnPassant(Square),
+    OpponentInCheck(Color, Square),
+}
+
+pub(crate) fn index((col, row): Square) -> usize {
+    row as usize * SIZE as usize + col as usize
+}
+
+pub(crate) fn is_piece(c: char) -> bool {
+    matches!(c.to_ascii_lowercase(), 'p' | 'r' | 'n' | 'b' | 'q' | 'k')
+}
+
+pub(crate) fn parse_square(name: &str) -> Option<Square> {
+    let mut chars = name.chars();
+    let col_char = chars.next()?.to_ascii_lowercase();
+    let row_char = chars.next()?;
+    if chars.next().is_some() {
+        return None;
+    }
+    let col = COL_CHARS.iter().position(|c| *c == col_char)?;
+    let row = ROW_CHARS.iter().position(|c| *c == row_char)?;
+    Some((col as u8, row as u8))
+}
+
+pub(crate) fn parse_placement(placement: &str) -> Result<Placement, PlacementError> {
+    let mut pieces = [None; 64];
+    let rank_count = placement.split('/').count();
+    if rank_count != SIZE as usize {
+        return Err(PlacementError::RankCount(rank_count));
+    }
+    let mut start = 0;
+    for (row, rank) in placement.split('/').enumerate() {
+        let mut col = 0u32;
+        for (offset, c) in rank.char_indices() {
+            let at = start + offset;
+            if let Some(empty) = c.to_digit(10).filter(|empty| (1..=8).contains(empty)) {
+                col += empty;
+            } else if is_piece(c) {
+                if col < SIZE as u32 {
+                    pieces[index((col as u8, row as u8))] = Some(c);
+                }
+                col += 1;
+            } else {
+                return Err(PlacementError::InvalidPiece(at..at + c.len_utf8()));
+            }
+        }
+        if col != SIZE as u32 {
+            return Err(PlacementError::InvalidRank(start..start + rank.len()));
+        }
+        start += rank.len() + 1;
+    }
+    Ok(pieces)
+}
+
+pub(crate) fn parse_castling(castling: &str) -> Option<Castling> {
+    let mut rights = [false; 4];
+    if castling == "-" {
+        return Some(rights);
+    }
+    if castling.is_empty() {
+        return None;
+    }
+    for c in castling.chars() {
+        let right = CASTLING_CHARS.iter().position(|fen_c| *fen_c == c)?;
+        if rights[right] {
+            return None;
+        }
+        rights[right] = true;
+    }
+    Some(rights)
+}
+
+pub(crate) fn validate(
+    pieces: &Placement,
+    turn: Color,
+    castling: Castling,
+    en_passant: Option<Square>,
+) -> Vec<Problem> {
+    let mut problems = vec![];
+    let squares = || (0..SIZE).flat_map(|row| (0..SIZE).map(move |col| (col, row)));
+    for color in [Color::White, Color::Black] {
+        let count = |kind: char| {
+            squares()
+                .filter(|square| pieces[index(*square)] == Some(color.piece(kind)))
+                .count()
+        };
+        let kings: Vec<Square> = squares()
+            .filter(|square| pieces[index(*square)] == Some(color.piece('k')))
+            .collect();
+        match kings.len() {
+            0 => problems.push(Problem::MissingKing(color)),
+            1 => {}
+            _ => problems.push(Problem::TooManyKings(color, kings[1])),
+        }
+        if count('p') > 8 {
+            problems.push(Problem::TooManyPawns(color));
+        }
+        let total = squares()
+            .filter(|square| pieces[index(*square)].is_some_and(|c| Color::of(c) == color))
+            .count();
+        if total > 16 {
+            problems.push(Problem::TooManyPieces(color));
+        }
+    }
+    for square in squares() {
+        if matches!(pieces[index(square)], Some('p' | 'P')) && (square.1 == 0 || square.1 == 7) {
+            problems.push(Problem::PawnOnBackRank(square));
+        }
+    }
+    for (right, has) in castling.iter().enumerate() {
+        let color = Color::of(CASTLING_CHARS[right]);
+        let rook_col = if right % 2 == 0 { 7 } else { 0 };
+        if *has
+            && (pieces[index((4, color.back_row()))] != Some(color.piece('k'))
+                || pieces[index((rook_col, color.back_row()))] != Some(color.piece('r')))
+        {
+            problems.push(Problem::InvalidCastling(right));
+        }
+    }
+    if let Some(target) = en_passant {
+        if !is_valid_en_passant_target(pieces, turn, target) {
+            problems.push(Problem::InvalidEnPassant(target));
+        }
+    }
+    let opponent = turn.opposite();
+    for square in squares() {
+        if pieces[index(square)] == Some(opponent.piece('k')) && is_attacked(pieces, square, turn) {
+            problems.push(Problem::OpponentInCheck(opponent, square));
+            break;
+        }
+    }
+    problems
+}
+
+fn is_valid_en_passant_target(pieces: &Placement, turn: Color, (col, row): Square) -> bool {
+    let opponent = turn.opposite();
+    let origin_row = opponent.pawn_row();
+    let target_row = (origin_row as i8 + opponent.forward()) as u8;
+    let pawn_row = (target_row as i8 + opponent.forward()) as u8;
+    row == target_row
+        && pieces[index((col, row))].is_none()
+        && pieces[index((col, origin_row))].is_none()
+        && pieces[index((col, pawn_row))] == Some(opponent.piece('p'))
+}
+
+fn offset((col, row): Square, (dc, dr): (i8, i8)) -> Option<Square> {
+    let col = col as i8 + dc;
+    let row = row as i8 + dr;
+    if (0..SIZE as i8).contains(&col) && (0..SIZE as i8).contains(&row) {
+        Some((col as u8, row as u8))
+    } else {
+        None
+    }
+}
+
+fn is_attacked(pieces: &Placement, square: Square, by: Color) -> bool {
+    let piece_at = |delta: (i8, i8)| offset(square, delta).and_then(|at| pieces[index(at)]);
+    let pawn = by.piece('p');
+    if [(-1, -by.forward()), (1, -by.forward())]
+        .iter()
+        .any(|delta| piece_at(*delta) == Some(pawn))
+    {
+        return true;
+    }
+    if offsets::KNIGHT
+        .iter()
+        .any(|delta| piece_at(*delta) == Some(by.piece('n')))
+        || offsets::KING
+            .iter()
+            .any(|delta| piece_at(*delta) == Some(by.piece('k')))
+    {
+        return true;
+    }
+    for (directions, slider) in [(offsets::ROOK, 'r'), (offsets::BISHOP, 'b')] {
+        for direction in directions {
+            let mut at = square;
+            while let Some(next) = offset(at, direction) {
+                at = next;
+                if let Some(piece) = pieces[index(at)] {
+                    if piece == by.piece(slider) || piece == by.piece('q') {
+                        return true;
+                    }
+                    break;
+                }
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_castling, parse_placement, parse_square, validate, Color, PlacementError, Problem,
+    };
+
+    const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+
+    #[test]
+    fn placement_errors_locate_the_bad_token() {
+        assert!(parse_placement(START).is_ok());
+        assert_eq!(
+            parse_placement("8/8/8/8/8/8/8"),
+            Err(PlacementError::RankCount(7))
+        );
+        assert_eq!(
+            parse_placement("4k3/8/8/2x5/8/8/8/4K3"),
+            Err(PlacementError::InvalidPiece(9..10))
+        );
+        assert_eq!(
+            parse_placement("4k3/8/8/8/ppppppppp/8/8/4K3"),
+            Err(PlacementError::InvalidRank(10..19))
+        );
+        assert_eq!(parse_castling("KQkq"), Some([true; 4]));
+        assert_eq!(parse_castling("KK"), None);
+        assert_eq!(parse_square("e3"), Some((4, 5)));
+        assert_eq!(parse_square("e9"), None);
+    }
+
+    #[test]
+    fn validation_finds_every_problem() {
+        let pieces = parse_placement(START).unwrap();
+        assert_eq!(validate(&pieces, Color::White, [true; 4], None), vec![]);
+        let pieces = parse_placement("4k3/8/8/8/8/8/4Q3/P3K2R").unwrap();
+        assert_eq!(
+            validate(
+                &pieces,
+                Color::White,
+                [true, true, false, false],
+                Some((3, 5))
+            ),
+            vec![
+                Problem::PawnOnBackRank((0, 7)),
+                Problem::InvalidCastling(1),
+                Problem::InvalidEnPassant((3, 5)),
+                Problem::OpponentInCheck(Color::Black, (4, 0)),
+            ]
+        );
+        let pieces = parse_placement("k7/8/8/8/8/8/8/8").unwrap();
+        assert_eq!(
+            validate(&pieces, Color::Black, [false; 4], None),
+            vec![Problem::MissingKing(Color::White)]
+        );
+    }
+}
@@ -0,0 +1,29 @@
+// Shared with chess_macros, which includes this file with `#[path]` so that
+// its check detection uses the same tables as `attacks`. Offsets are
+// `(col, row)` deltas.
+
+pub(crate) const KNIGHT: [(i8, i8); 8] = [
+    (-2, -1),
+    (-1, -2),
+    (-2, 1),
+    (-1, 2),
+    (2, -1),
+    (1, -2),
+    (2, 1),
+    (1, 2),
+];
+
+pub(crate) const KING: [(i8, i8); 8] = [
+    (0, -1),
+    (0, 1),
+    (-1, 0),
+    (1, 0),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+pub(crate) const ROOK: [(i8, i8); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+pub(crate) const BISHOP: [(i8, i8); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
@@ -1,4 +1,4 @@
-use crate::{constants::SQUARE_COUNT, Board, BoardMove, Position, PositionOffset, Uc};
+use crate::{constants::SQUARE_COUNT, Board, BoardMove, Ic, Position, PositionOffset, Uc};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Piece {
@@ -20,6 +20,24 @@ impl PieceColor {
             PieceColor::Black => PieceColor::White,
         }
     }
+
+    pub fn forward(&self) -> Ic {
+        match self {
+            PieceColor::White => -1,
+            PieceColor::Black => 1,
+        }
+    }
+
+    pub fn home_row(&self) -> Uc {
+        match self {
+            PieceColor::White => Position::row_count() - 1,
+            PieceColor::Black => 0,
+        }
+    }
+
+    pub fn pawn_row(&self) -> Uc {
+        (self.home_row() as Ic + self.forward()) as Uc
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -32,6 +50,21 @@ pub enum PieceKind {
     King,
 }
 
+impl PieceKind {
+    pub const ALL: [PieceKind; 6] = [
+        PieceKind::Pawn,
+        PieceKind::Rook,
+        PieceKind::Knight,
+        PieceKind::Bishop,
+        PieceKind::Queen,
+        PieceKind::King,
+    ];
+
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
 impl Piece {
     pub fn new(kind: PieceKind, color: PieceColor) -> Self {
         Self { kind, color }
@@ -45,6 +78,10 @@ impl Piece {
         self.color
     }
 
+    pub fn flipped(&self) -> Piece {
+        Piece::new(self.kind, self.color.opposite())
+    }
+
     pub fn value(&self) -> Uc {
         match self.color() {
             PieceColor::White => match self.kind {
@@ -66,6 +103,7 @@ impl Piece {
         }
     }
 
+    #[deprecated(note = "uses lowercase for White like `board!`; use `fen_char` for FEN case")]
     pub fn char(&self) -> char {
         match self.color() {
             PieceColor::White => match self.kind {
@@ -87,20 +125,47 @@ impl Piece {
         }
     }
 
+    pub fn fen_char(&self) -> char {
+        let c = match self.kind {
+            PieceKind::Pawn => 'p',
+            PieceKind::Rook => 'r',
+            PieceKind::Knight => 'n',
+            PieceKind::Bishop => 'b',
+            PieceKind::Queen => 'q',
+            PieceKind::King => 'k',
+        };
+        match self.color() {
+            PieceColor::White => c.to_ascii_uppercase(),
+            PieceColor::Black => c,
+        }
+    }
+
+    pub fn from_fen_char(c: char) -> Option<Piece> {
+        let kind = match c.to_ascii_lowercase() {
+            'p' => PieceKind::Pawn,
+            'r' => PieceKind::Rook,
+            'n' => PieceKind::Knight,
+            'b' => PieceKind::Bishop,
+            'q' => PieceKind::Queen,
+            'k' => PieceKind::King,
+            _ => return None,
+        };
+        let color = if c.is_ascii_uppercase() {
+            PieceColor::White
+        } else {
+            PieceColor::Black
+        };
+        Some(Piece::new(kind, color))
+    }
+
     pub(crate) fn moves<'a>(&self, planner: &mut PieceMovePlanner<'a>) {
         match self.kind() {
-            PieceKind::Pawn => match self.color() {
-                PieceColor::White => {
-                    planner.try_add_no_take(PositionOffset(0, -1));
-                    planner.try_add_take_only(PositionOffset(-1, -1));
-                    planner.try_add_take_only(PositionOffset(1, -1));
-                }
-                PieceColor::Black => {
-                    planner.try_add_no_take(PositionOffset(0, 1));
-                    planner.try_add_take_only(PositionOffset(-1, 1));
-                    planner.try_add_take_only(PositionOffset(1, 1));
-                }
-            },
+            PieceKind::Pawn => {
+                let forward = self.color().forward();
+                planner.try_add_no_take(PositionOffset(0, forward));
+                planner.try_add_take_only(PositionOffset(-1, forward));
+                planner.try_add_take_only(PositionOffset(1, forward));
+            }
             PieceKind::Rook => {
                 planner.try_add_directional_take(PositionOffset(0, -1));
                 planner.try_add_directional_take(PositionOffset(0, 1));
@@ -230,15 +295,11 @@ impl<'a> PieceMovePlanner<'a> {
     }
 
     pub(crate) fn is_under_attack(&self, offset: PositionOffset) -> bool {
-        let valid_moves = self.board.moves_internal(self.color.opposite(), false);
         if let Some(position) = self.position.offset(offset) {
-            for valid_move in valid_moves.iter() {
-                if valid_move.to == position {
-                    return true;
-                }
-            }
+            self.board.is_attacked(position, self.color.opposite())
+        } else {
+            false
         }
-        false
     }
 
     pub(crate) fn is_my_piece(&self, offset: PositionOffset, kind: PieceKind) -> bool {
@@ -252,23 +313,13 @@ impl<'a> PieceMovePlanner<'a> {
 
     pub(crate) fn is_empty(&self, offset: PositionOffset) -> bool {
         if let Some(position) = self.position.offset(offset) {
-            if let Some(..) = self.board.piece(position) {
-                false
-            } else {
-                true
-            }
+            self.board.piece(position).is_none()
         } else {
             false
         }
     }
 
-    pub(crate) fn moves(&self) -> Vec<BoardMove> {
-        let mut moves = vec![];
-        for i in 0..SQUARE_COUNT {
-            if let Some(board_move) = &self.moves[i] {
-                moves.push(*board_move);
-            }
-        }
-        moves
+    pub(crate) fn into_moves(self) -> [Option<BoardMove>; SQUARE_COUNT] {
+        self.moves
     }
 }
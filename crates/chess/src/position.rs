@@ -1,8 +1,9 @@
 use crate::{
     constants::{COL_CHARS, COL_COUNT, ROW_CHARS, ROW_COUNT, SQUARE_COUNT},
-    Ic, Uc,
+    notation, Ic, PieceColor, Uc,
 };
-use lazy_static::lazy_static;
+use alloc::string::{String, ToString};
+use core::fmt;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Position(pub Uc, pub Uc);
@@ -28,8 +29,16 @@ impl Position {
         ROW_CHARS[row as usize]
     }
 
+    pub fn from_name(name: &str) -> Option<Position> {
+        notation::parse_square(name).map(|(col, row)| Position(col, row))
+    }
+
+    pub fn name(&self) -> String {
+        self.to_string().to_ascii_lowercase()
+    }
+
     pub fn index(&self) -> usize {
-        return (self.0 + self.1 * COL_COUNT) as usize;
+        (self.0 + self.1 * COL_COUNT) as usize
     }
 
     pub fn col(&self) -> Uc {
@@ -40,6 +49,76 @@ impl Position {
         self.1
     }
 
+    pub fn row_positions(row: Uc) -> impl Iterator<Item = Position> {
+        (0..COL_COUNT).map(move |col| Position(col, row))
+    }
+
+    pub fn col_positions(col: Uc) -> impl Iterator<Item = Position> {
+        (0..ROW_COUNT).map(move |row| Position(col, row))
+    }
+
+    pub fn between(a: Position, b: Position) -> impl Iterator<Item = Position> {
+        let direction = a.direction(b);
+        let mut next = direction.and_then(|direction| a.offset(direction));
+        core::iter::from_fn(move || {
+            let position = next.filter(|position| *position != b)?;
+            next = position.offset(direction?);
+            Some(position)
+        })
+    }
+
+    pub fn line_through(a: Position, b: Position) -> impl Iterator<Item = Position> {
+        let direction = a.direction(b);
+        let mut next = direction.map(|direction| {
+            let backwards = PositionOffset(-direction.col(), -direction.row());
+            let mut start = a;
+            while let Some(position) = start.offset(backwards) {
+                start = position;
+            }
+            start
+        });
+        core::iter::from_fn(move || {
+            let position = next?;
+            next = position.offset(direction?);
+            Some(position)
+        })
+    }
+
+    pub fn direction(&self, other: Position) -> Option<PositionOffset> {
+        let col = other.col() as Ic - self.col() as Ic;
+        let row = other.row() as Ic - self.row() as Ic;
+        if *self == other || (col != 0 && row != 0 && col.abs() != row.abs()) {
+            return None;
+        }
+        Some(PositionOffset(col.signum(), row.signum()))
+    }
+
+    pub fn distance(&self, other: Position) -> Uc {
+        self.col()
+            .abs_diff(other.col())
+            .max(self.row().abs_diff(other.row()))
+    }
+
+    pub fn manhattan_distance(&self, other: Position) -> Uc {
+        self.col().abs_diff(other.col()) + self.row().abs_diff(other.row())
+    }
+
+    pub fn is_light_square(&self) -> bool {
+        (self.col() + self.row()) % 2 == 0
+    }
+
+    pub fn relative_row(&self, color: PieceColor) -> Uc {
+        self.row().abs_diff(color.home_row())
+    }
+
+    pub fn flipped(&self) -> Position {
+        Position(self.col(), ROW_COUNT - 1 - self.row())
+    }
+
+    pub fn mirrored(&self) -> Position {
+        Position(COL_COUNT - 1 - self.col(), self.row())
+    }
+
     pub fn offset(&self, offset: PositionOffset) -> Option<Position> {
         let new_col = self.col() as Ic + offset.col();
         let new_row = self.row() as Ic + offset.row();
@@ -55,9 +134,10 @@ impl Position {
     }
 }
 
-impl ToString for Position {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "{}{}",
             Position::col_char(self.col()),
             Position::row_char(self.row())
@@ -65,17 +145,15 @@ impl ToString for Position {
     }
 }
 
-lazy_static! {
-    static ref POSITIONS: [Position; SQUARE_COUNT] = {
-        let mut positions = [Position(0, 0); SQUARE_COUNT];
-        for col in 0..COL_COUNT {
-            for row in 0..ROW_COUNT {
-                positions[(col + row * COL_COUNT) as usize] = Position(col, row);
-            }
-        }
-        positions
-    };
-}
+const POSITIONS: [Position; SQUARE_COUNT] = {
+    let mut positions = [Position(0, 0); SQUARE_COUNT];
+    let mut index = 0;
+    while index < SQUARE_COUNT {
+        positions[index] = Position(index as Uc % COL_COUNT, index as Uc / COL_COUNT);
+        index += 1;
+    }
+    positions
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct PositionOffset(pub Ic, pub Ic);
@@ -89,3 +167,83 @@ impl PositionOffset {
         self.1
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Position;
+    use crate::{PieceColor, PositionOffset};
+    use alloc::vec::Vec;
+
+    fn names(positions: impl Iterator<Item = Position>) -> Vec<String> {
+        positions.map(|position| position.name()).collect()
+    }
+
+    #[test]
+    fn between_aligned_squares() {
+        let a1 = Position::from_name("a1").unwrap();
+        let d4 = Position::from_name("d4").unwrap();
+        let a4 = Position::from_name("a4").unwrap();
+        assert_eq!(names(Position::between(a1, d4)), ["b2", "c3"]);
+        assert_eq!(names(Position::between(d4, a1)), ["c3", "b2"]);
+        assert_eq!(names(Position::between(a1, a4)), ["a2", "a3"]);
+        assert!(Position::between(a1, Position::from_name("b3").unwrap())
+            .next()
+            .is_none());
+        assert!(Position::between(a1, a1).next().is_none());
+        assert_eq!(a1.direction(d4), Some(PositionOffset(1, -1)));
+    }
+
+    #[test]
+    fn line_through_spans_the_board() {
+        let c3 = Position::from_name("c3").unwrap();
+        let e5 = Position::from_name("e5").unwrap();
+        assert_eq!(
+            names(Position::line_through(c3, e5)),
+            ["a1", "b2", "c3", "d4", "e5", "f6", "g7", "h8"]
+        );
+        assert_eq!(
+            names(Position::line_through(e5, c3)),
+            ["h8", "g7", "f6", "e5", "d4", "c3", "b2", "a1"]
+        );
+        assert_eq!(
+            names(Position::line_through(
+                c3,
+                Position::from_name("c7").unwrap()
+            )),
+            ["c1", "c2", "c3", "c4", "c5", "c6", "c7", "c8"]
+        );
+        assert!(
+            Position::line_through(c3, Position::from_name("d5").unwrap())
+                .next()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn distances_and_colors() {
+        let a1 = Position::from_name("a1").unwrap();
+        let h8 = Position::from_name("h8").unwrap();
+        let b3 = Position::from_name("b3").unwrap();
+        assert_eq!(a1.distance(h8), 7);
+        assert_eq!(a1.manhattan_distance(h8), 14);
+        assert_eq!(a1.distance(b3), 2);
+        assert_eq!(a1.manhattan_distance(b3), 3);
+        assert!(!a1.is_light_square());
+        assert!(!h8.is_light_square());
+        assert!(Position::from_name("h1").unwrap().is_light_square());
+        assert!(Position::from_name("d1").unwrap().is_light_square());
+        assert_eq!(a1.relative_row(PieceColor::White), 0);
+        assert_eq!(a1.relative_row(PieceColor::Black), 7);
+        assert_eq!(b3.relative_row(PieceColor::White), 2);
+    }
+
+    #[test]
+    fn row_and_col_positions() {
+        assert_eq!(
+            names(Position::row_positions(0)),
+            ["a8", "b8", "c8", "d8", "e8", "f8", "g8", "h8"]
+        );
+        assert_eq!(names(Position::col_positions(4)).len(), 8);
+        assert!(Position::col_positions(4).all(|position| position.col() == 4));
+    }
+}
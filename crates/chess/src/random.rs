@@ -0,0 +1,117 @@
+use crate::{Board, BoardBuilder, Material, Piece, PieceColor, PieceKind, Position};
+use alloc::vec::Vec;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+const PLACEMENT_ATTEMPTS: usize = 1000;
+
+pub fn seeded(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+pub fn playout<R: Rng + ?Sized>(rng: &mut R, plies: u32) -> Board {
+    let mut board = Board::new();
+    for _ in 0..plies {
+        let moves = board.valid_moves(board.turn());
+        match moves.choose(rng) {
+            Some(board_move) => board.force_move(*board_move),
+            None => break,
+        }
+    }
+    board
+}
+
+pub fn from_material<R: Rng + ?Sized>(
+    rng: &mut R,
+    material: &Material,
+    turn: PieceColor,
+) -> Option<Board> {
+    let mut pieces = Vec::new();
+    for color in [PieceColor::White, PieceColor::Black] {
+        for kind in PieceKind::ALL {
+            for _ in 0..material.count(color, kind) {
+                pieces.push(Piece::new(kind, color));
+            }
+        }
+    }
+    if pieces.len() > Position::all().len() {
+        return None;
+    }
+    for _ in 0..PLACEMENT_ATTEMPTS {
+        let mut squares: Vec<Position> = Position::all().to_vec();
+        let mut builder = BoardBuilder::new();
+        builder.turn(turn);
+        let placed = pieces.iter().all(|piece| {
+            let candidates: Vec<usize> = (0..squares.len())
+                .filter(|index| {
+                    piece.kind() != PieceKind::Pawn
+                        || (squares[*index].row() != 0
+                            && squares[*index].row() != Position::row_count() - 1)
+                })
+                .collect();
+            match candidates.choose(rng) {
+                Some(index) => {
+                    builder.piece(squares.swap_remove(*index), Some(*piece));
+                    true
+                }
+                None => false,
+            }
+        });
+        if !placed {
+            return None;
+        }
+        if let Ok(board) = builder.build() {
+            return Some(board);
+        }
+    }
+    None
+}
+
+pub fn from_signature<R: Rng + ?Sized>(
+    rng: &mut R,
+    signature: &str,
+    turn: PieceColor,
+) -> Option<Board> {
+    from_material(rng, &Material::from_signature(signature)?, turn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_signature, playout, seeded};
+    use crate::{Board, PieceColor};
+
+    #[test]
+    fn playout_is_legal_and_reproducible() {
+        let board = playout(&mut seeded(7), 20);
+        assert_eq!(board.to_fen(), playout(&mut seeded(7), 20).to_fen());
+        assert!(Board::from_fen(&board.to_fen()).is_ok());
+        assert_eq!(playout(&mut seeded(7), 0), Board::new());
+        let mut rng = seeded(1);
+        let fens: Vec<String> = (0..4).map(|_| playout(&mut rng, 12).to_fen()).collect();
+        assert!(fens.iter().any(|fen| *fen != fens[0]));
+    }
+
+    #[test]
+    fn signature_positions_are_legal() {
+        let mut rng = seeded(3);
+        for _ in 0..50 {
+            let board = from_signature(&mut rng, "KRPvKR", PieceColor::Black).unwrap();
+            assert_eq!(board.material().signature(), "KRPvKR");
+            assert_eq!(board.turn(), PieceColor::Black);
+            assert!(!board.check(PieceColor::White));
+            assert!(Board::from_fen(&board.to_fen()).is_ok());
+        }
+        assert_eq!(
+            from_signature(&mut seeded(3), "KQvKR", PieceColor::White).map(|board| board.to_fen()),
+            from_signature(&mut seeded(3), "KQvKR", PieceColor::White).map(|board| board.to_fen())
+        );
+    }
+
+    #[test]
+    fn invalid_signatures() {
+        let mut rng = seeded(0);
+        assert!(from_signature(&mut rng, "KRvX", PieceColor::White).is_none());
+        assert!(from_signature(&mut rng, "KRvKK", PieceColor::White).is_none());
+        assert!(from_signature(&mut rng, "KPPPPPPPPPvK", PieceColor::White).is_none());
+    }
+}
@@ -1,9 +1,74 @@
 use crate::{
     constants::SQUARE_COUNT, piece::PieceMovePlanner, BoardMove, Piece, PieceColor, PieceKind,
-    PositionOffset,
+    Position, PositionOffset,
 };
 use chess_macros::board_move;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CastlingSide {
+    King,
+    Queen,
+}
+
+impl CastlingSide {
+    pub fn rook_position(&self, color: PieceColor) -> Position {
+        match self {
+            CastlingSide::King => Position(7, color.home_row()),
+            CastlingSide::Queen => Position(0, color.home_row()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct CastlingRights {
+    rights: [bool; 4],
+}
+
+impl CastlingRights {
+    pub fn all() -> Self {
+        Self { rights: [true; 4] }
+    }
+
+    pub fn none() -> Self {
+        Self { rights: [false; 4] }
+    }
+
+    pub fn king_position(color: PieceColor) -> Position {
+        Position(4, color.home_row())
+    }
+
+    fn index(color: PieceColor, side: CastlingSide) -> usize {
+        match (color, side) {
+            (PieceColor::White, CastlingSide::King) => 0,
+            (PieceColor::White, CastlingSide::Queen) => 1,
+            (PieceColor::Black, CastlingSide::King) => 2,
+            (PieceColor::Black, CastlingSide::Queen) => 3,
+        }
+    }
+
+    pub fn has(&self, color: PieceColor, side: CastlingSide) -> bool {
+        self.rights[Self::index(color, side)]
+    }
+
+    pub fn set(&mut self, color: PieceColor, side: CastlingSide, allowed: bool) {
+        self.rights[Self::index(color, side)] = allowed;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rights.iter().all(|allowed| !allowed)
+    }
+
+    pub fn flipped(&self) -> CastlingRights {
+        let mut rights = CastlingRights::none();
+        for color in [PieceColor::White, PieceColor::Black] {
+            for side in [CastlingSide::King, CastlingSide::Queen] {
+                rights.set(color.opposite(), side, self.has(color, side));
+            }
+        }
+        rights
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct Castle {
     moved: [bool; SQUARE_COUNT],
@@ -16,12 +81,38 @@ impl Castle {
         }
     }
 
-    pub(crate) fn moves<'a>(
-        &self,
-        piece: &Piece,
-        planner: &mut PieceMovePlanner<'a>,
-        king_check: bool,
-    ) {
+    pub fn from_rights(rights: CastlingRights) -> Self {
+        let mut moved = [true; SQUARE_COUNT];
+        for color in [PieceColor::White, PieceColor::Black] {
+            for side in [CastlingSide::King, CastlingSide::Queen] {
+                if rights.has(color, side) {
+                    moved[CastlingRights::king_position(color).index()] = false;
+                    moved[side.rook_position(color).index()] = false;
+                }
+            }
+        }
+        Self { moved }
+    }
+
+    pub fn rights(&self, pieces: &[Option<Piece>; SQUARE_COUNT]) -> CastlingRights {
+        let mut rights = CastlingRights::none();
+        let is_unmoved = |position: Position, kind: PieceKind, color: PieceColor| {
+            !self.moved[position.index()]
+                && pieces[position.index()] == Some(Piece::new(kind, color))
+        };
+        for color in [PieceColor::White, PieceColor::Black] {
+            for side in [CastlingSide::King, CastlingSide::Queen] {
+                if is_unmoved(CastlingRights::king_position(color), PieceKind::King, color)
+                    && is_unmoved(side.rook_position(color), PieceKind::Rook, color)
+                {
+                    rights.set(color, side, true);
+                }
+            }
+        }
+        rights
+    }
+
+    pub(crate) fn moves<'a>(&self, piece: &Piece, planner: &mut PieceMovePlanner<'a>) {
         if let PieceKind::King = piece.kind() {
             if planner.is_empty(PositionOffset(1, 0))
                 && planner.is_empty(PositionOffset(2, 0))
@@ -29,10 +120,11 @@ impl Castle {
                 && !planner.is_under_attack(PositionOffset(0, 0))
             {
                 let rook_position = planner.position().offset(PositionOffset(3, 0)).unwrap();
-                if !self.moved[planner.position().index()] && !self.moved[rook_position.index()] {
-                    if king_check && !planner.is_under_attack(PositionOffset(1, 0)) {
-                        planner.try_add_no_take(PositionOffset(2, 0));
-                    }
+                if !self.moved[planner.position().index()]
+                    && !self.moved[rook_position.index()]
+                    && !planner.is_under_attack(PositionOffset(1, 0))
+                {
+                    planner.try_add_no_take(PositionOffset(2, 0));
                 }
             }
             if planner.is_empty(PositionOffset(-1, 0))
@@ -42,10 +134,11 @@ impl Castle {
                 && !planner.is_under_attack(PositionOffset(0, 0))
             {
                 let rook_position = planner.position().offset(PositionOffset(-4, 0)).unwrap();
-                if !self.moved[planner.position().index()] && !self.moved[rook_position.index()] {
-                    if king_check && !planner.is_under_attack(PositionOffset(-1, 0)) {
-                        planner.try_add_no_take(PositionOffset(-2, 0));
-                    }
+                if !self.moved[planner.position().index()]
+                    && !self.moved[rook_position.index()]
+                    && !planner.is_under_attack(PositionOffset(-1, 0))
+                {
+                    planner.try_add_no_take(PositionOffset(-2, 0));
                 }
             }
         }
@@ -122,19 +215,16 @@ mod tests {
             r _ _ _ k _ _ r
         );
         assert!(board.apply_move(board_move!(e1 g1)));
-        assert_eq!(
-            board,
-            board!(
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                r _ _ _ _ r k _
-            )
-        );
+        assert!(board.same_placement(&board!(
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            r _ _ _ _ r k _
+        )));
     }
 
     #[test]
@@ -150,19 +240,16 @@ mod tests {
             r _ _ _ k _ _ r
         );
         assert!(board.apply_move(board_move!(e1 c1)));
-        assert_eq!(
-            board,
-            board!(
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ k r _ _ _ r
-            )
-        );
+        assert!(board.same_placement(&board!(
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ k r _ _ _ r
+        )));
     }
 
     #[test]
@@ -223,7 +310,7 @@ mod tests {
 
     #[test]
     fn castle_requires_rook() {
-        let mut board = board!(
+        let board = board!(
             _ _ _ _ K _ _ _
             _ _ _ _ _ _ _ _
             _ _ _ _ _ _ _ _
@@ -353,4 +440,23 @@ mod tests {
         assert!(!board.is_valid_move(board_move!(e1 c1)));
         assert!(!board.is_valid_move(board_move!(e1 g1)));
     }
+
+    #[test]
+    fn castle_respects_castling_clause() {
+        let board = board!(
+            R _ _ _ K _ _ R
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            r _ _ _ k _ _ r,
+            castling: Kq
+        );
+        assert!(board.is_valid_move(board_move!(e1 g1)));
+        assert!(!board.is_valid_move(board_move!(e1 c1)));
+        assert!(board.is_valid_move(board_move!(e8 c8)));
+        assert!(!board.is_valid_move(board_move!(e8 g8)));
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    constants::SQUARE_COUNT, piece::PieceMovePlanner, BoardMove, Piece, PieceColor, PieceKind,
-    PositionOffset,
+    constants::SQUARE_COUNT, piece::PieceMovePlanner, BoardMove, Ic, Piece, PieceColor, PieceKind,
+    Position, PositionOffset, Uc,
 };
 
 #[derive(Clone, Copy)]
@@ -15,49 +15,50 @@ impl EnPassant {
         }
     }
 
+    pub fn from_target(target: Option<Position>) -> Self {
+        Self {
+            last_pawn_move: target.and_then(|target| {
+                [PieceColor::White, PieceColor::Black]
+                    .into_iter()
+                    .find(|color| target.row() as Ic == color.pawn_row() as Ic + color.forward())
+                    .map(|color| BoardMove {
+                        from: Position(target.col(), color.pawn_row()),
+                        to: Position(target.col(), (target.row() as Ic + color.forward()) as Uc),
+                    })
+            }),
+        }
+    }
+
+    pub fn target(&self) -> Option<Position> {
+        if let Some(last_pawn_move) = self.last_pawn_move {
+            if last_pawn_move.from.col() == last_pawn_move.to.col()
+                && (last_pawn_move.from.row() as i8 - last_pawn_move.to.row() as i8).abs() == 2
+            {
+                return Some(Position(
+                    last_pawn_move.from.col(),
+                    (last_pawn_move.from.row() + last_pawn_move.to.row()) / 2,
+                ));
+            }
+        }
+        None
+    }
+
     pub(crate) fn moves<'a>(&self, piece: &Piece, planner: &mut PieceMovePlanner<'a>) {
         if let Some(last_pawn_move) = self.last_pawn_move {
             if let PieceKind::Pawn = piece.kind() {
-                match piece.color() {
-                    PieceColor::White => {
-                        if planner.position().row() == 3
-                            && last_pawn_move.from.row() == 1
-                            && last_pawn_move.to.row() == 3
+                let opponent = piece.color().opposite();
+                let forward = piece.color().forward();
+                if last_pawn_move.from.row() == opponent.pawn_row()
+                    && last_pawn_move.to.row() == planner.position().row()
+                    && (last_pawn_move.to.row() as Ic - last_pawn_move.from.row() as Ic)
+                        == opponent.forward() * 2
+                {
+                    for side in [-1, 1] {
+                        if let Some(passant) =
+                            planner.position().offset(PositionOffset(side, forward))
                         {
-                            if let Some(left_passant) =
-                                planner.position().offset(PositionOffset(-1, -1))
-                            {
-                                if left_passant.col() == last_pawn_move.to.col() {
-                                    planner.try_add_no_take(PositionOffset(-1, -1));
-                                }
-                            }
-                            if let Some(right_passant) =
-                                planner.position().offset(PositionOffset(1, -1))
-                            {
-                                if right_passant.col() == last_pawn_move.to.col() {
-                                    planner.try_add_no_take(PositionOffset(1, -1));
-                                }
-                            }
-                        }
-                    }
-                    PieceColor::Black => {
-                        if planner.position().row() == 4
-                            && last_pawn_move.from.row() == 6
-                            && last_pawn_move.to.row() == 4
-                        {
-                            if let Some(left_passant) =
-                                planner.position().offset(PositionOffset(-1, 1))
-                            {
-                                if left_passant.col() == last_pawn_move.to.col() {
-                                    planner.try_add_no_take(PositionOffset(-1, 1));
-                                }
-                            }
-                            if let Some(right_passant) =
-                                planner.position().offset(PositionOffset(1, -1))
-                            {
-                                if right_passant.col() == last_pawn_move.to.col() {
-                                    planner.try_add_no_take(PositionOffset(1, 1));
-                                }
+                            if passant.col() == last_pawn_move.to.col() {
+                                planner.try_add_no_take(PositionOffset(side, forward));
                             }
                         }
                     }
@@ -73,12 +74,11 @@ impl EnPassant {
     ) {
         if let Some(last_pawn_move) = self.last_pawn_move {
             if let Some(piece) = &pieces[board_move.from.index()] {
-                if piece.kind() == PieceKind::Pawn {
-                    if board_move.from.col() != board_move.to.col()
-                        && pieces[board_move.to.index()].is_none()
-                    {
-                        pieces[last_pawn_move.to.index()] = None;
-                    }
+                if piece.kind() == PieceKind::Pawn
+                    && board_move.from.col() != board_move.to.col()
+                    && pieces[board_move.to.index()].is_none()
+                {
+                    pieces[last_pawn_move.to.index()] = None;
                 }
             }
         }
@@ -98,7 +98,7 @@ impl EnPassant {
 
 #[cfg(test)]
 mod tests {
-    use chess_macros::{board, board_move};
+    use chess_macros::{board, board_move, fen};
 
     #[test]
     fn en_passant_white_left() {
@@ -114,19 +114,16 @@ mod tests {
         );
         assert!(board.apply_move(board_move!(d7 d5)));
         assert!(board.apply_move(board_move!(e5 d6)));
-        assert_eq!(
-            board,
-            board!(
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ p _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-            )
-        );
+        assert!(board.same_placement(&board!(
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ p _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+        )));
     }
 
     #[test]
@@ -143,19 +140,16 @@ mod tests {
         );
         assert!(board.apply_move(board_move!(e7 e5)));
         assert!(board.apply_move(board_move!(d5 e6)));
-        assert_eq!(
-            board,
-            board!(
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ p _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-            )
-        );
+        assert!(board.same_placement(&board!(
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ p _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+        )));
     }
 
     #[test]
@@ -215,4 +209,30 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn en_passant_from_ep_clause() {
+        let mut board = board!(
+            _ _ _ _ K _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ P p _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ k _ _ _,
+            white_to_move,
+            ep: d6
+        );
+        assert!(board.apply_move(board_move!(e5 d6)));
+        assert_eq!(board, fen!("4k3/8/3P4/8/8/8/8/4K3 b - - 0 1"));
+    }
+
+    #[test]
+    fn en_passant_expires_from_fen() {
+        let board = fen!("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1");
+        assert!(!board.is_valid_move(board_move!(e5 d6)));
+        let board = fen!("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+        assert!(board.is_valid_move(board_move!(e5 d6)));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{piece::PieceMovePlanner, Piece, PieceColor, PieceKind, PositionOffset};
+use crate::{piece::PieceMovePlanner, Piece, PieceKind, PositionOffset};
 
 #[derive(Clone, Copy)]
 pub struct PawnFirstMove;
@@ -10,21 +10,11 @@ impl PawnFirstMove {
 
     pub(crate) fn moves<'a>(&self, piece: &Piece, planner: &mut PieceMovePlanner<'a>) {
         if let PieceKind::Pawn = piece.kind() {
-            match piece.color() {
-                PieceColor::White => {
-                    if planner.position().row() == 6 {
-                        if planner.try_add_no_take(PositionOffset(0, -1)) {
-                            planner.try_add_no_take(PositionOffset(0, -2));
-                        }
-                    }
-                }
-                PieceColor::Black => {
-                    if planner.position().row() == 1 {
-                        if planner.try_add_no_take(PositionOffset(0, 1)) {
-                            planner.try_add_no_take(PositionOffset(0, 2));
-                        }
-                    }
-                }
+            let forward = piece.color().forward();
+            if planner.position().row() == piece.color().pawn_row()
+                && planner.try_add_no_take(PositionOffset(0, forward))
+            {
+                planner.try_add_no_take(PositionOffset(0, forward * 2));
             }
         }
     }
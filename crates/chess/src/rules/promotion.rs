@@ -1,4 +1,4 @@
-use crate::{constants::SQUARE_COUNT, Piece, PieceColor, PieceKind, Position};
+use crate::{constants::SQUARE_COUNT, Piece, PieceKind, Position};
 
 #[derive(Clone, Copy)]
 pub struct Promotion;
@@ -12,20 +12,7 @@ impl Promotion {
         for position in Position::all().iter() {
             if let Some(piece) = &mut pieces[position.index()] {
                 if let PieceKind::Pawn = piece.kind() {
-                    let mut promote = false;
-                    match piece.color() {
-                        PieceColor::White => {
-                            if position.row() == 0 {
-                                promote = true;
-                            }
-                        }
-                        PieceColor::Black => {
-                            if position.row() == Position::row_count() - 1 {
-                                promote = true;
-                            }
-                        }
-                    }
-                    if promote {
+                    if position.row() == piece.color().opposite().home_row() {
                         pieces[position.index()] =
                             Some(Piece::new(PieceKind::Queen, piece.color()));
                     }
@@ -52,19 +39,16 @@ mod tests {
             _ _ _ _ _ _ _ _
         );
         assert!(board.apply_move(board_move!(a7 a8)));
-        assert_eq!(
-            board,
-            board!(
-                q _ _ _ _ _ _ P
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-                _ _ _ _ _ _ _ _
-            )
-        );
+        assert!(board.same_placement(&board!(
+            q _ _ _ _ _ _ P
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+        )));
     }
 
     #[test]
@@ -0,0 +1,191 @@
+use crate::{
+    constants::ROW_CHARS, Board, BoardMove, CastlingRights, Piece, PieceKind, Position, Uc,
+};
+use alloc::{string::String, vec::Vec};
+
+const SAN_PIECES: [(PieceKind, char); 5] = [
+    (PieceKind::King, 'K'),
+    (PieceKind::Queen, 'Q'),
+    (PieceKind::Rook, 'R'),
+    (PieceKind::Bishop, 'B'),
+    (PieceKind::Knight, 'N'),
+];
+
+impl Board {
+    pub fn san(&self, board_move: BoardMove) -> String {
+        let piece = match self.piece(board_move.from) {
+            Some(piece) => piece,
+            None => return board_move.coordinate(),
+        };
+        let mut san = String::new();
+        let col_diff = board_move.to.col() as i8 - board_move.from.col() as i8;
+        if piece.kind() == PieceKind::King && col_diff.abs() == 2 {
+            san.push_str(if col_diff > 0 { "O-O" } else { "O-O-O" });
+        } else {
+            let capture = self.piece(board_move.to).is_some()
+                || (piece.kind() == PieceKind::Pawn && col_diff != 0);
+            if let Some((_, c)) = SAN_PIECES.iter().find(|(kind, _)| *kind == piece.kind()) {
+                san.push(*c);
+                let others: Vec<Position> = self
+                    .moves_to(board_move.to)
+                    .filter(|other| {
+                        other.from != board_move.from && self.piece(other.from) == Some(piece)
+                    })
+                    .map(|other| other.from)
+                    .collect();
+                if !others.is_empty() {
+                    let same_col = others
+                        .iter()
+                        .any(|from| from.col() == board_move.from.col());
+                    let same_row = others
+                        .iter()
+                        .any(|from| from.row() == board_move.from.row());
+                    if !same_col || same_row {
+                        san.push(file_char(board_move.from));
+                    }
+                    if same_col {
+                        san.push(Position::row_char(board_move.from.row()));
+                    }
+                }
+            } else if capture {
+                san.push(file_char(board_move.from));
+            }
+            if capture {
+                san.push('x');
+            }
+            san.push_str(&board_move.to.name());
+            if piece.kind() == PieceKind::Pawn
+                && board_move.to.row() == piece.color().opposite().home_row()
+            {
+                san.push_str("=Q");
+            }
+        }
+        let mut after = *self;
+        after.force_move(board_move);
+        let opponent = piece.color().opposite();
+        if after.check(opponent) {
+            san.push(if after.moves(opponent).next().is_none() {
+                '#'
+            } else {
+                '+'
+            });
+        }
+        san
+    }
+
+    pub fn parse_san(&self, san: &str) -> Option<BoardMove> {
+        let san = san.trim_end_matches(['+', '#', '!', '?']);
+        let color = self.turn();
+        let king = CastlingRights::king_position(color);
+        let castle_col = match san {
+            "O-O" | "0-0" => Some(king.col() + 2),
+            "O-O-O" | "0-0-0" => Some(king.col() - 2),
+            _ => None,
+        };
+        if let Some(col) = castle_col {
+            let board_move = BoardMove {
+                from: king,
+                to: Position(col, king.row()),
+            };
+            let is_king = self.piece(king) == Some(Piece::new(PieceKind::King, color));
+            return (is_king && self.is_valid_move(board_move)).then_some(board_move);
+        }
+        let (san, promotion) = match san.split_once('=') {
+            Some((san, promotion)) => (san, Some(promotion)),
+            None => (san, None),
+        };
+        if promotion.is_some_and(|promotion| promotion != "Q") {
+            return None;
+        }
+        let (kind, rest) = match SAN_PIECES.iter().find(|(_, c)| san.starts_with(*c)) {
+            Some((kind, _)) => (*kind, &san[1..]),
+            None => (PieceKind::Pawn, san),
+        };
+        if rest.len() < 2 || !rest.is_char_boundary(rest.len() - 2) {
+            return None;
+        }
+        let to = Position::from_name(&rest[rest.len() - 2..])?;
+        let mut from_col = None;
+        let mut from_row = None;
+        for c in rest[..rest.len() - 2].chars() {
+            match c {
+                'x' => {}
+                'a'..='h' if from_col.is_none() => {
+                    from_col = Some(c as u8 - b'a');
+                }
+                '1'..='8' if from_row.is_none() => {
+                    from_row = ROW_CHARS
+                        .iter()
+                        .position(|row| *row == c)
+                        .map(|row| row as Uc);
+                }
+                _ => return None,
+            }
+        }
+        let promotes = kind == PieceKind::Pawn && to.row() == color.opposite().home_row();
+        if promotion.is_some() && !promotes {
+            return None;
+        }
+        let mut candidates = self.moves_to(to).filter(|board_move| {
+            self.piece(board_move.from)
+                .is_some_and(|piece| piece.kind() == kind && piece.color() == color)
+                && from_col.map_or(true, |col| board_move.from.col() == col)
+                && from_row.map_or(true, |row| board_move.from.row() == row)
+        });
+        let board_move = candidates.next()?;
+        if candidates.next().is_some() {
+            return None;
+        }
+        Some(board_move)
+    }
+}
+
+fn file_char(position: Position) -> char {
+    Position::col_char(position.col()).to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Board;
+    use chess_macros::{board_move, fen};
+
+    #[test]
+    fn san_round_trip() {
+        let board = Board::new();
+        assert_eq!(board.parse_san("Nf3"), Some(board_move!(g1 f3)));
+        assert_eq!(board.parse_san("e4"), Some(board_move!(e2 e4)));
+        assert_eq!(board.san(board_move!(g1 f3)), "Nf3");
+        assert_eq!(board.san(board_move!(e2 e4)), "e4");
+        assert_eq!(board.parse_san("e5"), None);
+        assert_eq!(board.parse_san("Nf6"), None);
+    }
+
+    #[test]
+    fn san_disambiguation() {
+        let board = fen!("4k3/8/8/8/8/8/4K3/R6R w - - 0 1");
+        assert_eq!(board.parse_san("Rd1"), None);
+        assert_eq!(board.parse_san("Rad1"), Some(board_move!(a1 d1)));
+        assert_eq!(board.parse_san("Rhf1"), Some(board_move!(h1 f1)));
+        assert_eq!(board.san(board_move!(a1 d1)), "Rad1");
+        let board = fen!("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1");
+        assert_eq!(board.parse_san("R1a3"), Some(board_move!(a1 a3)));
+        assert_eq!(board.san(board_move!(a5 a3)), "R5a3");
+    }
+
+    #[test]
+    fn san_special_moves() {
+        let board = fen!("r3k2r/4P3/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1");
+        assert_eq!(board.parse_san("O-O"), Some(board_move!(e1 g1)));
+        assert_eq!(board.parse_san("O-O-O"), Some(board_move!(e1 c1)));
+        assert_eq!(board.san(board_move!(e1 g1)), "O-O");
+        assert_eq!(board.parse_san("exd6"), Some(board_move!(e5 d6)));
+        assert_eq!(board.san(board_move!(e5 d6)), "exd6");
+        assert_eq!(board.parse_san("e8=Q+"), None);
+        assert_eq!(board.parse_san("exf8=N"), None);
+        let board = fen!("7k/4P3/8/8/8/8/8/K7 w - - 0 1");
+        assert_eq!(board.parse_san("e8=Q+"), Some(board_move!(e7 e8)));
+        assert_eq!(board.san(board_move!(e7 e8)), "e8=Q+");
+        let board = fen!("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        assert_eq!(board.san(board_move!(a1 a8)), "Ra8#");
+    }
+}
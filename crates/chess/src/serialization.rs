@@ -0,0 +1,136 @@
+use crate::{Board, BoardMove, Piece, PieceColor, PieceKind, Position};
+use alloc::{format, string::String};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+impl Serialize for Position {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Position {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Position::from_name(&name)
+            .ok_or_else(|| D::Error::custom(format!("invalid square `{}`", name)))
+    }
+}
+
+impl Serialize for BoardMove {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.coordinate())
+    }
+}
+
+impl<'de> Deserialize<'de> for BoardMove {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let coordinate = String::deserialize(deserializer)?;
+        BoardMove::from_coordinate(&coordinate)
+            .ok_or_else(|| D::Error::custom(format!("invalid move `{}`", coordinate)))
+    }
+}
+
+impl Serialize for Piece {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(self.fen_char())
+    }
+}
+
+impl<'de> Deserialize<'de> for Piece {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        Piece::from_fen_char(c).ok_or_else(|| D::Error::custom(format!("invalid piece `{}`", c)))
+    }
+}
+
+impl Serialize for PieceKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(Piece::new(*self, PieceColor::Black).fen_char())
+    }
+}
+
+impl<'de> Deserialize<'de> for PieceKind {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        Piece::from_fen_char(c.to_ascii_lowercase())
+            .map(|piece| piece.kind())
+            .ok_or_else(|| D::Error::custom(format!("invalid piece kind `{}`", c)))
+    }
+}
+
+impl Serialize for PieceColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(match self {
+            PieceColor::White => 'w',
+            PieceColor::Black => 'b',
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for PieceColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match char::deserialize(deserializer)? {
+            'w' => Ok(PieceColor::White),
+            'b' => Ok(PieceColor::Black),
+            c => Err(D::Error::custom(format!("invalid color `{}`", c))),
+        }
+    }
+}
+
+impl Serialize for Board {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_fen())
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let fen = String::deserialize(deserializer)?;
+        Board::from_fen(&fen).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, BoardMove, Piece, PieceColor, PieceKind, Position};
+    use chess_macros::{board_move, fen};
+
+    #[test]
+    fn serialize_compact_strings() {
+        assert_eq!(serde_json::to_string(&Position(4, 4)).unwrap(), "\"e4\"");
+        assert_eq!(
+            serde_json::to_string(&board_move!(e2 e4)).unwrap(),
+            "\"e2e4\""
+        );
+        assert_eq!(
+            serde_json::to_string(&Piece::new(PieceKind::Knight, PieceColor::Black)).unwrap(),
+            "\"n\""
+        );
+        assert_eq!(serde_json::to_string(&PieceKind::Queen).unwrap(), "\"q\"");
+        assert_eq!(serde_json::to_string(&PieceColor::White).unwrap(), "\"w\"");
+    }
+
+    #[test]
+    fn board_round_trips_rules_state() {
+        let mut board = fen!("r3k2r/8/8/8/4p3/8/3P4/R3K2R w Kq - 4 30");
+        assert!(board.apply_move(board_move!(d2 d4)));
+        let json = serde_json::to_string(&board).unwrap();
+        assert_eq!(json, "\"r3k2r/8/8/8/3Pp3/8/8/R3K2R b Kq d3 0 30\"");
+        let restored: Board = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, board);
+        assert_eq!(restored.to_fen(), board.to_fen());
+        assert!(restored.is_valid_move(board_move!(e4 d3)));
+        assert!(restored.is_valid_move(board_move!(e8 c8)));
+        assert!(!restored.is_valid_move(board_move!(e8 g8)));
+    }
+
+    #[test]
+    fn deserialize_rejects_invalid_values() {
+        assert!(serde_json::from_str::<Position>("\"i9\"").is_err());
+        assert!(serde_json::from_str::<BoardMove>("\"e2\"").is_err());
+        assert!(serde_json::from_str::<Piece>("\"x\"").is_err());
+        assert!(serde_json::from_str::<Board>("\"8/8/8/8/8/8/8/8 w - - 0 1\"").is_err());
+        let moves: Vec<BoardMove> = serde_json::from_str("[\"e2e4\", \"g8f6\"]").unwrap();
+        assert_eq!(moves, vec![board_move!(e2 e4), board_move!(g8 f6)]);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{constants::SQUARE_COUNT, Board, Position};
+
+impl Board {
+    pub fn flipped(&self) -> Board {
+        let mut pieces = [None; SQUARE_COUNT];
+        for position in Position::all().iter() {
+            pieces[position.flipped().index()] = self.piece(*position).map(|piece| piece.flipped());
+        }
+        Board::from_parts(
+            pieces,
+            self.turn().opposite(),
+            self.castling_rights().flipped(),
+            self.en_passant_target().map(|target| target.flipped()),
+            self.halfmove_clock(),
+            self.fullmove_number(),
+        )
+    }
+
+    pub fn mirrored(&self) -> Option<Board> {
+        if !self.castling_rights().is_empty() {
+            return None;
+        }
+        let mut pieces = [None; SQUARE_COUNT];
+        for position in Position::all().iter() {
+            pieces[position.mirrored().index()] = self.piece(*position);
+        }
+        Some(Board::from_parts(
+            pieces,
+            self.turn(),
+            self.castling_rights(),
+            self.en_passant_target().map(|target| target.mirrored()),
+            self.halfmove_clock(),
+            self.fullmove_number(),
+        ))
+    }
+
+    pub fn canonical(&self) -> Board {
+        let board = if self.turn() == crate::PieceColor::Black {
+            self.flipped()
+        } else {
+            *self
+        };
+        match board.mirrored() {
+            Some(mirrored) if mirrored.canonical_key() < board.canonical_key() => mirrored,
+            _ => board,
+        }
+    }
+
+    fn canonical_key(&self) -> ([u8; SQUARE_COUNT], Option<usize>) {
+        let mut key = [0; SQUARE_COUNT];
+        for position in Position::all().iter() {
+            if let Some(piece) = self.piece(*position) {
+                key[position.index()] = 1 + piece.kind().index() as u8 * 2 + piece.color() as u8;
+            }
+        }
+        (key, self.en_passant_target().map(|target| target.index()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, PieceColor};
+    use chess_macros::{board_move, fen};
+
+    #[test]
+    fn flipped_swaps_colors_and_ranks() {
+        let board = fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kq d6 0 24");
+        let flipped = board.flipped();
+        assert_eq!(flipped.to_fen(), "r3k2r/8/8/8/3Pp3/8/8/R3K2R b Qk d3 0 24");
+        assert!(flipped.is_valid_move(board_move!(e4 d3)));
+        assert!(!flipped.is_valid_move(board_move!(e8 c8)));
+        assert!(flipped.is_valid_move(board_move!(e8 g8)));
+        assert_eq!(flipped.flipped(), board);
+        assert_eq!(
+            flipped.moves(flipped.turn()).count(),
+            board.moves(board.turn()).count()
+        );
+        assert_eq!(
+            Board::new().flipped(),
+            fen!("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1")
+        );
+        assert_ne!(Board::new().flipped(), Board::new());
+    }
+
+    #[test]
+    fn mirrored_requires_no_castling_rights() {
+        assert!(Board::new().mirrored().is_none());
+        let board = fen!("4k3/8/8/3pP3/8/8/8/R3K3 w - d6 0 1");
+        let mirrored = board.mirrored().unwrap();
+        assert_eq!(mirrored.to_fen(), "3k4/8/8/3Pp3/8/8/8/3K3R w - e6 0 1");
+        assert!(mirrored.is_valid_move(board_move!(d5 e6)));
+    }
+
+    #[test]
+    fn canonical_matches_symmetric_positions() {
+        let board = fen!("4k3/8/8/3pP3/8/8/8/R3K3 w - d6 0 1");
+        let canonical = board.canonical();
+        assert_eq!(canonical.turn(), PieceColor::White);
+        assert_eq!(
+            board.mirrored().unwrap().canonical().to_fen(),
+            canonical.to_fen()
+        );
+        assert_eq!(board.flipped().canonical().to_fen(), canonical.to_fen());
+        assert_eq!(
+            board.flipped().mirrored().unwrap().canonical().to_fen(),
+            canonical.to_fen()
+        );
+        assert_eq!(Board::new().canonical().to_fen(), Board::new().to_fen());
+    }
+}
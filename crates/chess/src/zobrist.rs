@@ -0,0 +1,135 @@
+use crate::{
+    constants::{COL_COUNT, SQUARE_COUNT},
+    Board, CastlingSide, Piece, PieceColor, PieceKind, Position,
+};
+
+const PIECE_KEYS: usize = SQUARE_COUNT * PieceKind::ALL.len() * 2;
+const TURN_KEY: usize = PIECE_KEYS;
+const CASTLING_KEYS: usize = TURN_KEY + 1;
+const EN_PASSANT_KEYS: usize = CASTLING_KEYS + 4;
+const KEY_COUNT: usize = EN_PASSANT_KEYS + COL_COUNT as usize;
+
+const KEYS: [u64; KEY_COUNT] = {
+    let mut keys = [0; KEY_COUNT];
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut index = 0;
+    while index < KEY_COUNT {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut key = state;
+        key = (key ^ (key >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        key = (key ^ (key >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        keys[index] = key ^ (key >> 31);
+        index += 1;
+    }
+    keys
+};
+
+fn piece_key(piece: Piece, position: Position) -> u64 {
+    let piece_index = piece.kind().index() * 2 + piece.color() as usize;
+    KEYS[piece_index * SQUARE_COUNT + position.index()]
+}
+
+impl Board {
+    pub fn hash(&self) -> u64 {
+        let mut hash = 0;
+        for position in Position::all().iter() {
+            if let Some(piece) = self.piece(*position) {
+                hash ^= piece_key(piece, *position);
+            }
+        }
+        if self.turn() == PieceColor::Black {
+            hash ^= KEYS[TURN_KEY];
+        }
+        let rights = self.castling_rights();
+        for (index, (color, side)) in [
+            (PieceColor::White, CastlingSide::King),
+            (PieceColor::White, CastlingSide::Queen),
+            (PieceColor::Black, CastlingSide::King),
+            (PieceColor::Black, CastlingSide::Queen),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            if rights.has(color, side) {
+                hash ^= KEYS[CASTLING_KEYS + index];
+            }
+        }
+        if let Some(target) = self.en_passant_target() {
+            hash ^= KEYS[EN_PASSANT_KEYS + target.col() as usize];
+        }
+        hash
+    }
+
+    pub fn pawn_hash(&self) -> u64 {
+        let mut hash = 0;
+        for position in Position::all().iter() {
+            if let Some(piece) = self.piece(*position) {
+                if piece.kind() == PieceKind::Pawn {
+                    hash ^= piece_key(piece, *position);
+                }
+            }
+        }
+        hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Board;
+    use chess_macros::{board_move, fen};
+
+    #[test]
+    fn null_move_passes_the_turn() {
+        let mut board = fen!("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 5");
+        board.null_move();
+        let expected = fen!("4k3/8/8/3pP3/8/8/8/4K3 b - - 1 5");
+        assert_eq!(board.to_fen(), expected.to_fen());
+        assert_eq!(board.hash(), expected.hash());
+        board.null_move();
+        assert_eq!(board.to_fen(), "4k3/8/8/3pP3/8/8/8/4K3 w - - 2 6");
+    }
+
+    #[test]
+    fn hash_matches_transpositions() {
+        let mut a = Board::new();
+        let mut b = Board::new();
+        for board_move in [board_move!(g1 f3), board_move!(g8 f6), board_move!(b1 c3)] {
+            assert!(a.apply_move(board_move));
+        }
+        for board_move in [board_move!(b1 c3), board_move!(g8 f6), board_move!(g1 f3)] {
+            assert!(b.apply_move(board_move));
+        }
+        assert_eq!(a.hash(), b.hash());
+        assert_eq!(a.hash(), Board::from_fen(&a.to_fen()).unwrap().hash());
+        assert_ne!(a.hash(), Board::new().hash());
+    }
+
+    #[test]
+    fn hash_includes_rules_state() {
+        let base = fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq - 0 1").hash();
+        assert_ne!(base, fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R b KQkq - 0 1").hash());
+        assert_ne!(base, fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kkq - 0 1").hash());
+        assert_ne!(
+            base,
+            fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").hash()
+        );
+        assert_eq!(
+            base,
+            fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq - 7 30").hash()
+        );
+    }
+
+    #[test]
+    fn pawn_hash_ignores_pieces() {
+        let board = fen!("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq - 0 1");
+        assert_eq!(
+            board.pawn_hash(),
+            fen!("4k3/8/8/3pP3/8/8/8/4K3 b - - 0 1").pawn_hash()
+        );
+        assert_ne!(
+            board.pawn_hash(),
+            fen!("r3k2r/8/8/3p4/4P3/8/8/R3K2R w KQkq - 0 1").pawn_hash()
+        );
+        assert_eq!(fen!("4k3/8/8/8/8/8/8/4K3 w - - 0 1").pawn_hash(), 0);
+    }
+}
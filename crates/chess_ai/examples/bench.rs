@@ -0,0 +1,26 @@
+use chess_ai::SearchFeatures;
+use std::time::Instant;
+
+fn main() {
+    let depth = std::env::args()
+        .nth(1)
+        .and_then(|depth| depth.parse().ok())
+        .unwrap_or(3);
+    for (name, move_ordering, features) in [
+        ("unordered", false, SearchFeatures::none()),
+        ("ordered", true, SearchFeatures::none()),
+        ("selective", true, SearchFeatures::default()),
+    ] {
+        let start = Instant::now();
+        let report = chess_ai::bench(&chess_ai::BENCH_POSITIONS, depth, |searcher| {
+            searcher.move_ordering(move_ordering).features(features)
+        });
+        println!(
+            "{}: {} positions, {} nodes, {:.2?}",
+            name,
+            report.positions,
+            report.nodes,
+            start.elapsed()
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{is_mate_score, AiConfig, MATE_SCORE};
+use chess::{Board, BoardMove};
+use rand::Rng;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveScore {
+    Centipawns(i32),
+    Mate(i32),
+}
+
+impl From<i32> for MoveScore {
+    fn from(score: i32) -> Self {
+        if is_mate_score(score) {
+            let moves = (MATE_SCORE - score.abs() + 1) / 2;
+            MoveScore::Mate(if score > 0 { moves } else { -moves })
+        } else {
+            MoveScore::Centipawns(score)
+        }
+    }
+}
+
+impl fmt::Display for MoveScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveScore::Centipawns(centipawns) => {
+                write!(f, "{:+.2}", *centipawns as f64 / 100.)
+            }
+            MoveScore::Mate(moves) => write!(f, "#{}", moves),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoredMove {
+    pub board_move: BoardMove,
+    pub score: MoveScore,
+    pub depth: u32,
+    pub pv: Vec<BoardMove>,
+}
+
+pub fn analyze(board: &Board, config: &AiConfig, count: usize) -> Vec<ScoredMove> {
+    analyze_with_rng(board, config, count, &mut rand::thread_rng())
+}
+
+// Every line is deepened together so scores are only ranked against lines of
+// the same depth. An iteration cut short by the budget is dropped, unless it
+// is the first one.
+pub fn analyze_with_rng<R: Rng + ?Sized>(
+    board: &Board,
+    config: &AiConfig,
+    count: usize,
+    rng: &mut R,
+) -> Vec<ScoredMove> {
+    let color = board.turn();
+    let count = count.min(board.moves(color).count());
+    let mut searcher = config.searcher(board, color, rng);
+    let mut lines: Vec<(i32, ScoredMove)> = vec![];
+    searcher.start();
+    for depth in 1..=config.depth.max(1) {
+        let mut iteration: Vec<(i32, ScoredMove)> = vec![];
+        while iteration.len() < count {
+            let excluded: Vec<BoardMove> =
+                iteration.iter().map(|(_, line)| line.board_move).collect();
+            let previous_pv = lines
+                .iter()
+                .find(|(_, line)| !excluded.contains(&line.board_move))
+                .map_or(vec![], |(_, line)| line.pv.clone());
+            let result = searcher.search_line(depth, excluded, previous_pv);
+            if searcher.is_stopped() {
+                break;
+            }
+            let Some(board_move) = result.best_move else {
+                break;
+            };
+            iteration.push((
+                result.score,
+                ScoredMove {
+                    board_move,
+                    score: MoveScore::from(result.score),
+                    depth,
+                    pv: result.pv,
+                },
+            ));
+        }
+        iteration.sort_by_key(|(score, _)| -score);
+        if searcher.is_stopped() {
+            if lines.is_empty() {
+                lines = iteration;
+            }
+            break;
+        }
+        lines = iteration;
+        if searcher.half_budget_used() {
+            break;
+        }
+    }
+    lines.into_iter().map(|(_, line)| line).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze, analyze_with_rng, MoveScore};
+    use crate::{AiConfig, Difficulty, Searcher, MATE_SCORE, MAX_PLY};
+    use chess::{board_move, fen, Board, PieceColor};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn config(depth: u32) -> AiConfig {
+        AiConfig {
+            depth,
+            ..AiConfig::default()
+        }
+    }
+
+    #[test]
+    fn ranks_distinct_candidates() {
+        let board = fen!("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+        let lines = analyze(&board, &config(2), 3);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].board_move, board_move!(d1 d5));
+        assert_eq!(lines[0].pv[0], lines[0].board_move);
+        assert_ne!(lines[1].board_move, lines[0].board_move);
+        assert_ne!(lines[2].board_move, lines[1].board_move);
+        assert_ne!(lines[2].board_move, lines[0].board_move);
+        let scores: Vec<i32> = lines
+            .iter()
+            .map(|line| match line.score {
+                MoveScore::Centipawns(centipawns) => centipawns,
+                MoveScore::Mate(_) => panic!("unexpected mate score"),
+            })
+            .collect();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!(scores[0] > scores[1] + 500);
+    }
+
+    #[test]
+    fn reports_mates_and_limits_count() {
+        let board = fen!("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        let lines = analyze(&board, &config(3), 2);
+        assert_eq!(lines[0].board_move, board_move!(a1 a8));
+        assert_eq!(lines[0].score, MoveScore::Mate(1));
+        assert_eq!(lines[0].score.to_string(), "#1");
+        let board = fen!("7k/8/8/8/8/8/8/K7 w - - 0 1");
+        assert_eq!(analyze(&board, &config(1), 10).len(), 3);
+        assert!(analyze(&fen!("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"), &config(1), 3).is_empty());
+        assert_eq!(analyze(&Board::new(), &config(1), 0), vec![]);
+    }
+
+    #[test]
+    fn seeded_analysis_is_reproducible() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let config = Difficulty::Beginner.config();
+        let lines = |seed| analyze_with_rng(&board, &config, 4, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(lines(7), lines(7));
+        assert!((0..10).any(|seed| lines(seed) != lines(7)));
+    }
+
+    #[test]
+    fn ranking_matches_fixed_depth_search() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let lines = analyze(&board, &config(3), 4);
+        let result = Searcher::new(&board, PieceColor::White).search(3);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].board_move, result.best_move.unwrap());
+        assert_eq!(lines[0].score, MoveScore::from(result.score));
+        assert_eq!(lines[0].pv, result.pv);
+        assert!(lines.iter().all(|line| line.depth == 3));
+    }
+
+    #[test]
+    fn budgeted_lines_share_one_depth() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let config = AiConfig {
+            depth: MAX_PLY,
+            node_limit: Some(5_000),
+            ..AiConfig::default()
+        };
+        let lines = analyze(&board, &config, 4);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].depth > 1);
+        assert!(lines.iter().all(|line| line.depth == lines[0].depth));
+        let starved = AiConfig {
+            node_limit: Some(10),
+            ..config
+        };
+        assert!(analyze(&board, &starved, 4).len() < 4);
+    }
+
+    #[test]
+    fn score_conversion() {
+        assert_eq!(MoveScore::from(35), MoveScore::Centipawns(35));
+        assert_eq!(MoveScore::from(35).to_string(), "+0.35");
+        assert_eq!(MoveScore::from(-120).to_string(), "-1.20");
+        assert_eq!(MoveScore::from(MATE_SCORE - 3), MoveScore::Mate(2));
+        assert_eq!(MoveScore::from(-MATE_SCORE + 2), MoveScore::Mate(-1));
+        assert_eq!(MoveScore::Mate(-1).to_string(), "#-1");
+    }
+}
@@ -0,0 +1,53 @@
+use crate::Searcher;
+use chess::Board;
+
+pub const BENCH_POSITIONS: [&str; 6] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "r2q1rk1/pp2bppp/2n1pn2/3p4/3P4/2NBPN2/PP3PPP/R2Q1RK1 b - - 3 10",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1",
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BenchReport {
+    pub positions: usize,
+    pub nodes: u64,
+}
+
+pub fn bench(
+    positions: &[&str],
+    depth: u32,
+    mut configure: impl FnMut(Searcher) -> Searcher,
+) -> BenchReport {
+    let mut report = BenchReport::default();
+    for fen in positions {
+        let board = Board::from_fen(fen).unwrap();
+        let result = configure(Searcher::new(&board, board.turn())).search(depth);
+        report.positions += 1;
+        report.nodes += result.nodes;
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bench, BENCH_POSITIONS};
+    use crate::SearchFeatures;
+
+    #[test]
+    fn move_ordering_reduces_nodes() {
+        let positions = [BENCH_POSITIONS[1], BENCH_POSITIONS[3]];
+        let ordered = bench(&positions, 2, |searcher| {
+            searcher.features(SearchFeatures::none())
+        });
+        let unordered = bench(&positions, 2, |searcher| {
+            searcher
+                .move_ordering(false)
+                .features(SearchFeatures::none())
+        });
+        assert_eq!(ordered.positions, 2);
+        assert!(ordered.nodes * 3 < unordered.nodes * 2);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{search::MAX_PLY, SearchFeatures, Searcher, TimeControl};
+use chess::{Board, PieceColor};
+use rand::Rng;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    Beginner,
+    Novice,
+    Intermediate,
+    #[default]
+    Advanced,
+    Maximum,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 5] = [
+        Difficulty::Beginner,
+        Difficulty::Novice,
+        Difficulty::Intermediate,
+        Difficulty::Advanced,
+        Difficulty::Maximum,
+    ];
+
+    pub fn config(&self) -> AiConfig {
+        match self {
+            Difficulty::Beginner => AiConfig {
+                depth: 1,
+                temperature: 150,
+                eval_noise: 100,
+                ..AiConfig::default()
+            },
+            Difficulty::Novice => AiConfig {
+                depth: 2,
+                temperature: 60,
+                eval_noise: 50,
+                ..AiConfig::default()
+            },
+            Difficulty::Intermediate => AiConfig {
+                depth: 3,
+                temperature: 20,
+                eval_noise: 20,
+                ..AiConfig::default()
+            },
+            Difficulty::Advanced => AiConfig::default(),
+            Difficulty::Maximum => AiConfig {
+                depth: MAX_PLY,
+                node_limit: Some(500_000),
+                ..AiConfig::default()
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AiConfig {
+    pub depth: u32,
+    pub node_limit: Option<u64>,
+    pub time_control: Option<TimeControl>,
+    pub temperature: u32,
+    pub eval_noise: i32,
+    pub features: SearchFeatures,
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self {
+            depth: crate::DEFAULT_DEPTH,
+            node_limit: None,
+            time_control: None,
+            temperature: 0,
+            eval_noise: 0,
+            features: SearchFeatures::default(),
+        }
+    }
+}
+
+impl AiConfig {
+    pub fn searcher<R: Rng + ?Sized>(
+        &self,
+        board: &Board,
+        color: PieceColor,
+        rng: &mut R,
+    ) -> Searcher {
+        Searcher::new(board, color)
+            .node_limit(self.node_limit)
+            .time_control(self.time_control)
+            .eval_noise(self.eval_noise, rng.gen())
+            .features(self.features)
+    }
+}
+
+impl From<Difficulty> for AiConfig {
+    fn from(difficulty: Difficulty) -> Self {
+        difficulty.config()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AiConfig, Difficulty};
+    use crate::{plan_move, plan_move_with_config, plan_move_with_rng};
+    use chess::{board_move, fen, Board, BoardMove, PieceColor};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn presets_increase_in_strength() {
+        for pair in Difficulty::ALL.windows(2) {
+            let (weaker, stronger) = (pair[0].config(), pair[1].config());
+            assert!(weaker.depth <= stronger.depth);
+            assert!(weaker.temperature >= stronger.temperature);
+            assert!(weaker.eval_noise >= stronger.eval_noise);
+        }
+        // Presets stay reproducible; a time control is opt-in.
+        assert!(Difficulty::ALL
+            .iter()
+            .all(|difficulty| difficulty.config().time_control.is_none()));
+        assert_eq!(AiConfig::from(Difficulty::default()), AiConfig::default());
+    }
+
+    #[test]
+    fn every_preset_plays_legal_moves() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        for difficulty in Difficulty::ALL {
+            let config = AiConfig {
+                node_limit: Some(2_000),
+                ..difficulty.config()
+            };
+            let board_move = plan_move_with_config(&board, PieceColor::White, &config).unwrap();
+            assert!(board.is_valid_move(board_move));
+        }
+        let stalemate = fen!("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+        assert_eq!(
+            plan_move_with_config(
+                &stalemate,
+                PieceColor::Black,
+                &Difficulty::Beginner.config()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn temperature_still_prefers_winning_moves() {
+        let board = fen!("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+        let config = Difficulty::Novice.config();
+        let wins = (0..5)
+            .filter(|seed| {
+                let mut rng = StdRng::seed_from_u64(*seed);
+                plan_move_with_rng(&board, PieceColor::White, &config, &mut rng)
+                    == Some(board_move!(d1 d5))
+            })
+            .count();
+        assert_eq!(wins, 5);
+        assert_eq!(
+            plan_move(&board, PieceColor::White),
+            Some(board_move!(d1 d5))
+        );
+    }
+
+    #[test]
+    fn temperature_falls_back_to_search_without_a_completed_iteration() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let config = AiConfig {
+            node_limit: Some(10),
+            ..Difficulty::Novice.config()
+        };
+        let board_move = plan_move_with_config(&board, PieceColor::White, &config).unwrap();
+        assert!(board.is_valid_move(board_move));
+    }
+
+    #[test]
+    fn seeded_games_are_reproducible() {
+        let play = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let config = Difficulty::Beginner.config();
+            let mut board = Board::new();
+            let mut moves: Vec<BoardMove> = vec![];
+            for _ in 0..6 {
+                let board_move =
+                    plan_move_with_rng(&board, board.turn(), &config, &mut rng).unwrap();
+                board.force_move(board_move);
+                moves.push(board_move);
+            }
+            moves
+        };
+        assert_eq!(play(42), play(42));
+        assert!((0..10).any(|seed| play(seed) != play(42)));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{search::MAX_PLY, Searcher, TimeControl};
+use chess::{BoardMove, Epd, EpdError};
+use std::{fmt, time::Duration};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchLimit {
+    Depth(u32),
+    Time(Duration),
+}
+
+#[derive(Clone, Debug)]
+pub struct EpdResult {
+    pub id: Option<String>,
+    pub fen: String,
+    pub best_moves: Vec<BoardMove>,
+    pub avoid_moves: Vec<BoardMove>,
+    pub chosen: Option<BoardMove>,
+    pub chosen_san: Option<String>,
+    pub solved: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EpdReport {
+    pub results: Vec<EpdResult>,
+}
+
+impl EpdReport {
+    pub fn solved(&self) -> usize {
+        self.results.iter().filter(|result| result.solved).count()
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &EpdResult> {
+        self.results.iter().filter(|result| !result.solved)
+    }
+}
+
+impl fmt::Display for EpdResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {}",
+            if self.solved { "solved" } else { "failed" },
+            self.id.as_deref().unwrap_or(&self.fen),
+            self.chosen_san.as_deref().unwrap_or("(none)")
+        )?;
+        for (opcode, moves) in [("bm", &self.best_moves), ("am", &self.avoid_moves)] {
+            if !moves.is_empty() {
+                write!(f, " {}", opcode)?;
+                for board_move in moves.iter() {
+                    write!(f, " {}", board_move.coordinate())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for EpdReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in self.results.iter() {
+            writeln!(f, "{}", result)?;
+        }
+        write!(f, "{}/{} solved", self.solved(), self.results.len())
+    }
+}
+
+pub fn run_epd(epd: &Epd, limit: SearchLimit) -> Result<EpdResult, EpdError> {
+    let best_moves = epd.best_moves()?;
+    let avoid_moves = epd.avoid_moves()?;
+    let board = epd.board();
+    let searcher = Searcher::new(board, board.turn());
+    let (mut searcher, depth) = match limit {
+        SearchLimit::Depth(depth) => (searcher, depth),
+        SearchLimit::Time(time) => (
+            searcher.time_control(Some(TimeControl::MoveTime(time))),
+            MAX_PLY,
+        ),
+    };
+    let chosen = searcher.search(depth).best_move;
+    let solved = chosen.is_some_and(|chosen| {
+        (best_moves.is_empty() || best_moves.contains(&chosen)) && !avoid_moves.contains(&chosen)
+    });
+    Ok(EpdResult {
+        id: epd.id().map(|id| id.to_owned()),
+        fen: board.to_fen(),
+        best_moves,
+        avoid_moves,
+        chosen,
+        chosen_san: chosen.map(|chosen| board.san(chosen)),
+        solved,
+    })
+}
+
+pub fn run_suite(suite: &str, limit: SearchLimit) -> Result<EpdReport, EpdError> {
+    let mut report = EpdReport::default();
+    for line in suite.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        report.results.push(run_epd(&Epd::parse(line)?, limit)?);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_suite, SearchLimit};
+    use chess::{board_move, EpdError};
+    use std::time::Duration;
+
+    const SUITE: &str = "
+        # forced replies
+        7k/8/8/8/8/8/6q1/7K w - - bm Kxg2; id \"forced.001\";
+        k7/1R6/8/8/8/8/8/7K b - - am Kxb7; id \"forced.002\";
+        7k/6Q1/8/8/8/8/8/K7 b - - bm Kxg7; id \"forced.003\";
+    ";
+
+    #[test]
+    fn suite_reports_solved_and_failed() {
+        let report = run_suite(SUITE, SearchLimit::Depth(1)).unwrap();
+        assert_eq!(report.results.len(), 3);
+        assert_eq!(report.solved(), 2);
+        let failed: Vec<_> = report.failed().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].id.as_deref(), Some("forced.002"));
+        assert_eq!(failed[0].chosen, Some(board_move!(a8 b7)));
+        assert_eq!(report.results[0].chosen_san.as_deref(), Some("Kxg2"));
+        assert_eq!(report.to_string().lines().last(), Some("2/3 solved"));
+        assert_eq!(
+            report.to_string().lines().next(),
+            Some("solved forced.001: Kxg2 bm h1g2")
+        );
+    }
+
+    #[test]
+    fn suite_with_time_limit() {
+        let report = run_suite(SUITE, SearchLimit::Time(Duration::from_millis(1))).unwrap();
+        assert_eq!(report.solved(), 2);
+    }
+
+    #[test]
+    fn suite_errors() {
+        assert_eq!(
+            run_suite("7k/8/8/8/8/8/6q1/7K w - - bm Kh2;", SearchLimit::Depth(0)).unwrap_err(),
+            EpdError::InvalidMove("Kh2".to_owned())
+        );
+    }
+}
@@ -0,0 +1,427 @@
+use crate::pawns::{self, PawnEntry, PawnTable};
+use chess::{
+    Board, PieceColor, PieceKind, Position, PositionOffset, BISHOP_DIRECTIONS, KING_OFFSETS,
+    KNIGHT_OFFSETS, ROOK_DIRECTIONS,
+};
+
+const PHASE_TOTAL: i32 = 24;
+const BISHOP_PAIR: Score = Score(30, 50);
+const ROOK_OPEN_FILE: Score = Score(25, 10);
+const ROOK_SEMI_OPEN_FILE: Score = Score(12, 6);
+const PAWN_SHIELD: i32 = 12;
+const KING_ZONE_ATTACK: i32 = 8;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Score(pub(crate) i32, pub(crate) i32);
+
+impl std::ops::Add for Score {
+    type Output = Score;
+
+    fn add(self, other: Score) -> Score {
+        Score(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+impl std::ops::AddAssign for Score {
+    fn add_assign(&mut self, other: Score) {
+        self.0 += other.0;
+        self.1 += other.1;
+    }
+}
+
+impl std::ops::SubAssign for Score {
+    fn sub_assign(&mut self, other: Score) {
+        self.0 -= other.0;
+        self.1 -= other.1;
+    }
+}
+
+impl std::ops::Mul<i32> for Score {
+    type Output = Score;
+
+    fn mul(self, factor: i32) -> Score {
+        Score(self.0 * factor, self.1 * factor)
+    }
+}
+
+pub fn piece_value(kind: PieceKind) -> i32 {
+    material(kind).0
+}
+
+fn material(kind: PieceKind) -> Score {
+    match kind {
+        PieceKind::Pawn => Score(82, 94),
+        PieceKind::Knight => Score(337, 281),
+        PieceKind::Bishop => Score(365, 297),
+        PieceKind::Rook => Score(477, 512),
+        PieceKind::Queen => Score(1025, 936),
+        PieceKind::King => Score(0, 0),
+    }
+}
+
+fn phase_weight(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::Knight | PieceKind::Bishop => 1,
+        PieceKind::Rook => 2,
+        PieceKind::Queen => 4,
+        PieceKind::Pawn | PieceKind::King => 0,
+    }
+}
+
+fn mobility_weight(kind: PieceKind) -> Score {
+    match kind {
+        PieceKind::Knight => Score(4, 4),
+        PieceKind::Bishop => Score(5, 5),
+        PieceKind::Rook => Score(2, 4),
+        PieceKind::Queen => Score(1, 2),
+        PieceKind::Pawn | PieceKind::King => Score(0, 0),
+    }
+}
+
+#[rustfmt::skip]
+const PAWN_MG: [i32; 64] = [
+      0,   0,   0,   0,   0,   0,   0,   0,
+     98, 134,  61,  95,  68, 126,  34, -11,
+     -6,   7,  26,  31,  65,  56,  25, -20,
+    -14,  13,   6,  21,  23,  12,  17, -23,
+    -27,  -2,  -5,  12,  17,   6,  10, -25,
+    -26,  -4,  -4, -10,   3,   3,  33, -12,
+    -35,  -1, -20, -23, -15,  24,  38, -22,
+      0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const PAWN_EG: [i32; 64] = [
+      0,   0,   0,   0,   0,   0,   0,   0,
+    178, 173, 158, 134, 147, 132, 165, 187,
+     94, 100,  85,  67,  56,  53,  82,  84,
+     32,  24,  13,   5,  -2,   4,  17,  17,
+     13,   9,  -3,  -7,  -7,  -8,   3,  -1,
+      4,   7,  -6,   1,   0,  -5,  -1,  -8,
+     13,   8,   8,  10,  13,   0,   2,  -7,
+      0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_MG: [i32; 64] = [
+   -167, -89, -34, -49,  61, -97, -15,-107,
+    -73, -41,  72,  36,  23,  62,   7, -17,
+    -47,  60,  37,  65,  84, 129,  73,  44,
+     -9,  17,  19,  53,  37,  69,  18,  22,
+    -13,   4,  16,  13,  28,  19,  21,  -8,
+    -23,  -9,  12,  10,  19,  17,  25, -16,
+    -29, -53, -12,  -3,  -1,  18, -14, -19,
+   -105, -21, -58, -33, -17, -28, -19, -23,
+];
+
+#[rustfmt::skip]
+const KNIGHT_EG: [i32; 64] = [
+    -58, -38, -13, -28, -31, -27, -63, -99,
+    -25,  -8, -25,  -2,  -9, -25, -24, -52,
+    -24, -20,  10,   9,  -1,  -9, -19, -41,
+    -17,   3,  22,  22,  22,  11,   8, -18,
+    -18,  -6,  16,  25,  16,  17,   4, -18,
+    -23,  -3,  -1,  15,  10,  -3, -20, -22,
+    -42, -20, -10,  -5,  -2, -20, -23, -44,
+    -29, -51, -23, -15, -22, -18, -50, -64,
+];
+
+#[rustfmt::skip]
+const BISHOP_MG: [i32; 64] = [
+    -29,   4, -82, -37, -25, -42,   7,  -8,
+    -26,  16, -18, -13,  30,  59,  18, -47,
+    -16,  37,  43,  40,  35,  50,  37,  -2,
+     -4,   5,  19,  50,  37,  37,   7,  -2,
+     -6,  13,  13,  26,  34,  12,  10,   4,
+      0,  15,  15,  15,  14,  27,  18,  10,
+      4,  15,  16,   0,   7,  21,  33,   1,
+    -33,  -3, -14, -21, -13, -12, -39, -21,
+];
+
+#[rustfmt::skip]
+const BISHOP_EG: [i32; 64] = [
+    -14, -21, -11,  -8,  -7,  -9, -17, -24,
+     -8,  -4,   7, -12,  -3, -13,  -4, -14,
+      2,  -8,   0,  -1,  -2,   6,   0,   4,
+     -3,   9,  12,   9,  14,  10,   3,   2,
+     -6,   3,  13,  19,   7,  10,  -3,  -9,
+    -12,  -3,   8,  10,  13,   3,  -7, -15,
+    -14, -18,  -7,  -1,   4,  -9, -15, -27,
+    -23,  -9, -23,  -5,  -9, -16,  -5, -17,
+];
+
+#[rustfmt::skip]
+const ROOK_MG: [i32; 64] = [
+     32,  42,  32,  51,  63,   9,  31,  43,
+     27,  32,  58,  62,  80,  67,  26,  44,
+     -5,  19,  26,  36,  17,  45,  61,  16,
+    -24, -11,   7,  26,  24,  35,  -8, -20,
+    -36, -26, -12,  -1,   9,  -7,   6, -23,
+    -45, -25, -16, -17,   3,   0,  -5, -33,
+    -44, -16, -20,  -9,  -1,  11,  -6, -71,
+    -19, -13,   1,  17,  16,   7, -37, -26,
+];
+
+#[rustfmt::skip]
+const ROOK_EG: [i32; 64] = [
+     13,  10,  18,  15,  12,  12,   8,   5,
+     11,  13,  13,  11,  -3,   3,   8,   3,
+      7,   7,   7,   5,   4,  -3,  -5,  -3,
+      4,   3,  13,   1,   2,   1,  -1,   2,
+      3,   5,   8,   4,  -5,  -6,  -8, -11,
+     -4,   0,  -5,  -1,  -7, -12,  -8, -16,
+     -6,  -6,   0,   2,  -9,  -9, -11,  -3,
+     -9,   2,   3,  -1,  -5, -13,   4, -20,
+];
+
+#[rustfmt::skip]
+const QUEEN_MG: [i32; 64] = [
+    -28,   0,  29,  12,  59,  44,  43,  45,
+    -24, -39,  -5,   1, -16,  57,  28,  54,
+    -13, -17,   7,   8,  29,  56,  47,  57,
+    -27, -27, -16, -16,  -1,  17,  -2,   1,
+     -9, -26,  -9, -10,  -2,  -4,   3,  -3,
+    -14,   2, -11,  -2,  -5,   2,  14,   5,
+    -35,  -8,  11,   2,   8,  15,  -3,   1,
+     -1, -18,  -9,  10, -15, -25, -31, -50,
+];
+
+#[rustfmt::skip]
+const QUEEN_EG: [i32; 64] = [
+     -9,  22,  22,  27,  27,  19,  10,  20,
+    -17,  20,  32,  41,  58,  25,  30,   0,
+    -20,   6,   9,  49,  47,  35,  19,   9,
+      3,  22,  24,  45,  57,  40,  57,  36,
+    -18,  28,  19,  47,  31,  34,  39,  23,
+    -16, -27,  15,   6,   9,  17,  10,   5,
+    -22, -23, -30, -16, -16, -23, -36, -32,
+    -33, -28, -22, -43,  -5, -32, -20, -41,
+];
+
+#[rustfmt::skip]
+const KING_MG: [i32; 64] = [
+    -65,  23,  16, -15, -56, -34,   2,  13,
+     29,  -1, -20,  -7,  -8,  -4, -38, -29,
+     -9,  24,   2, -16, -20,   6,  22, -22,
+    -17, -20, -12, -27, -30, -25, -14, -36,
+    -49,  -1, -27, -39, -46, -44, -33, -51,
+    -14, -14, -22, -46, -44, -30, -15, -27,
+      1,   7,  -8, -64, -43, -16,   9,   8,
+    -15,  36,  12, -54,   8, -28,  24,  14,
+];
+
+#[rustfmt::skip]
+const KING_EG: [i32; 64] = [
+    -74, -35, -18, -18, -11,  15,   4, -17,
+    -12,  17,  14,  17,  17,  38,  23,  11,
+     10,  17,  23,  15,  20,  45,  44,  13,
+     -8,  22,  24,  27,  26,  33,  26,   3,
+    -18,  -4,  21,  24,  27,  23,   9, -11,
+    -19,  -3,  11,  21,  23,  16,   7,  -9,
+    -27, -11,   4,  13,  14,   4,  -5, -17,
+    -53, -34, -21, -11, -28, -14, -24, -43,
+];
+
+fn piece_square(kind: PieceKind, color: PieceColor, position: Position) -> Score {
+    let index = match color {
+        PieceColor::White => position.index(),
+        PieceColor::Black => position.flipped().index(),
+    };
+    let (mg, eg) = match kind {
+        PieceKind::Pawn => (&PAWN_MG, &PAWN_EG),
+        PieceKind::Knight => (&KNIGHT_MG, &KNIGHT_EG),
+        PieceKind::Bishop => (&BISHOP_MG, &BISHOP_EG),
+        PieceKind::Rook => (&ROOK_MG, &ROOK_EG),
+        PieceKind::Queen => (&QUEEN_MG, &QUEEN_EG),
+        PieceKind::King => (&KING_MG, &KING_EG),
+    };
+    Score(mg[index], eg[index])
+}
+
+pub fn evaluate(board: &Board, color: PieceColor) -> i32 {
+    evaluate_entry(board, color, &pawns::analyze(board, board.pawn_hash()))
+}
+
+pub fn evaluate_with(board: &Board, color: PieceColor, pawn_table: &mut PawnTable) -> i32 {
+    evaluate_entry(board, color, &pawn_table.probe(board))
+}
+
+fn evaluate_entry(board: &Board, color: PieceColor, pawn_entry: &PawnEntry) -> i32 {
+    let mut score = Score::default();
+    let mut phase = 0;
+    for position in Position::all().iter() {
+        if let Some(piece) = board.piece(*position) {
+            phase += phase_weight(piece.kind());
+        }
+    }
+    for side in [PieceColor::White, PieceColor::Black] {
+        let side_score = side_score(board, side)
+            + pawn_entry.score(side)
+            + pawns::passed_path(board, pawn_entry, side);
+        if side == color {
+            score += side_score;
+        } else {
+            score -= side_score;
+        }
+    }
+    let phase = phase.min(PHASE_TOTAL);
+    (score.0 * phase + score.1 * (PHASE_TOTAL - phase)) / PHASE_TOTAL
+}
+
+fn side_score(board: &Board, color: PieceColor) -> Score {
+    let mut score = Score::default();
+    let mut bishops = 0;
+    let mut king = None;
+    for position in Position::all().iter() {
+        let piece = match board.piece(*position) {
+            Some(piece) if piece.color() == color => piece,
+            _ => continue,
+        };
+        score += material(piece.kind());
+        score += piece_square(piece.kind(), color, *position);
+        score += mobility_weight(piece.kind()) * mobility(board, *position, piece.kind(), color);
+        match piece.kind() {
+            PieceKind::Bishop => bishops += 1,
+            PieceKind::Rook => {
+                let (own, enemy) = file_pawns(board, position.col(), color);
+                if own == 0 && enemy == 0 {
+                    score += ROOK_OPEN_FILE;
+                } else if own == 0 {
+                    score += ROOK_SEMI_OPEN_FILE;
+                }
+            }
+            PieceKind::King => king = Some(*position),
+            _ => {}
+        }
+    }
+    if bishops >= 2 {
+        score += BISHOP_PAIR;
+    }
+    if let Some(king) = king {
+        score.0 += king_safety(board, king, color);
+    }
+    score
+}
+
+fn mobility(board: &Board, position: Position, kind: PieceKind, color: PieceColor) -> i32 {
+    let available = |to: Position| board.piece(to).map_or(true, |piece| piece.color() != color);
+    match kind {
+        PieceKind::Knight => KNIGHT_OFFSETS
+            .iter()
+            .filter_map(|offset| position.offset(*offset))
+            .filter(|to| available(*to))
+            .count() as i32,
+        PieceKind::Bishop => slider_mobility(board, position, &BISHOP_DIRECTIONS, available),
+        PieceKind::Rook => slider_mobility(board, position, &ROOK_DIRECTIONS, available),
+        PieceKind::Queen => {
+            slider_mobility(board, position, &BISHOP_DIRECTIONS, available)
+                + slider_mobility(board, position, &ROOK_DIRECTIONS, available)
+        }
+        PieceKind::Pawn | PieceKind::King => 0,
+    }
+}
+
+fn slider_mobility(
+    board: &Board,
+    position: Position,
+    directions: &[PositionOffset],
+    available: impl Fn(Position) -> bool,
+) -> i32 {
+    let mut count = 0;
+    for direction in directions {
+        let mut next = position.offset(*direction);
+        while let Some(to) = next {
+            if available(to) {
+                count += 1;
+            }
+            if board.piece(to).is_some() {
+                break;
+            }
+            next = to.offset(*direction);
+        }
+    }
+    count
+}
+
+fn file_pawns(board: &Board, col: u8, color: PieceColor) -> (u32, u32) {
+    let mut own = 0;
+    let mut enemy = 0;
+    for position in Position::col_positions(col) {
+        if let Some(piece) = board.piece(position) {
+            if piece.kind() == PieceKind::Pawn {
+                if piece.color() == color {
+                    own += 1;
+                } else {
+                    enemy += 1;
+                }
+            }
+        }
+    }
+    (own, enemy)
+}
+
+fn king_safety(board: &Board, king: Position, color: PieceColor) -> i32 {
+    let mut safety = 0;
+    for col in -1..=1 {
+        for row in 1..=2 {
+            let shield = king.offset(PositionOffset(col, color.forward() * row));
+            if shield
+                .and_then(|shield| board.piece(shield))
+                .is_some_and(|piece| piece.kind() == PieceKind::Pawn && piece.color() == color)
+            {
+                safety += PAWN_SHIELD / row as i32;
+            }
+        }
+    }
+    for offset in KING_OFFSETS {
+        if let Some(zone) = king.offset(offset) {
+            safety -= KING_ZONE_ATTACK * board.attacker_count(zone, color.opposite()) as i32;
+        }
+    }
+    safety
+}
+
+#[cfg(test)]
+mod tests {
+    use super::evaluate;
+    use chess::{fen, Board, PieceColor};
+
+    #[test]
+    fn starting_position_is_balanced() {
+        assert_eq!(evaluate(&Board::new(), PieceColor::White), 0);
+        assert_eq!(evaluate(&Board::new(), PieceColor::Black), 0);
+    }
+
+    #[test]
+    fn evaluation_is_symmetric() {
+        for board in [
+            fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4"),
+            fen!("8/5k2/3p4/1p1Pp2p/pP2Pp1P/P4P1K/8/8 b - - 0 1"),
+            fen!("r4rk1/1b3ppp/p7/1p6/8/1B3Q2/PPP2PPP/3R1RK1 w - - 0 1"),
+        ] {
+            let score = evaluate(&board, PieceColor::White);
+            assert_eq!(score, -evaluate(&board, PieceColor::Black));
+            assert_eq!(score, evaluate(&board.flipped(), PieceColor::Black));
+        }
+    }
+
+    #[test]
+    fn evaluation_terms() {
+        let pair = fen!("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1");
+        let single = fen!("4k3/8/8/8/8/8/8/2N1KB2 w - - 0 1");
+        assert!(evaluate(&pair, PieceColor::White) > evaluate(&single, PieceColor::White));
+        let open = fen!("4k3/pp3ppp/8/8/8/8/PP3PPP/3RK3 w - - 0 1");
+        let closed = fen!("4k3/pp2pppp/8/8/8/8/PP1P1PPP/2R1K3 w - - 0 1");
+        assert!(evaluate(&open, PieceColor::White) > evaluate(&closed, PieceColor::White));
+        let centralised = fen!("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1");
+        let cornered = fen!("4k3/8/8/8/8/8/8/N3K3 w - - 0 1");
+        assert!(evaluate(&centralised, PieceColor::White) > evaluate(&cornered, PieceColor::White));
+    }
+
+    #[test]
+    fn king_safety_prefers_pawn_shield() {
+        let sheltered = fen!("r5k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1");
+        let exposed = fen!("r5k1/5ppp/8/8/8/5PPP/8/R5K1 w - - 0 1");
+        assert!(evaluate(&sheltered, PieceColor::White) > evaluate(&exposed, PieceColor::White));
+    }
+}
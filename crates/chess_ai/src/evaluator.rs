@@ -0,0 +1,98 @@
+use crate::{eval::piece_value, evaluate_with, PawnTable};
+use chess::{Board, PieceColor, PieceKind, Position};
+
+const DEFAULT_PAWN_TABLE_MEGABYTES: usize = 1;
+
+pub trait Evaluator {
+    fn evaluate(&mut self, board: &Board, color: PieceColor) -> i32;
+}
+
+impl<F: FnMut(&Board, PieceColor) -> i32> Evaluator for F {
+    fn evaluate(&mut self, board: &Board, color: PieceColor) -> i32 {
+        self(board, color)
+    }
+}
+
+pub struct HandcraftedEvaluator {
+    pawn_table: PawnTable,
+}
+
+impl HandcraftedEvaluator {
+    pub fn new(pawn_table: PawnTable) -> Self {
+        Self { pawn_table }
+    }
+}
+
+impl Default for HandcraftedEvaluator {
+    fn default() -> Self {
+        Self::new(PawnTable::new(DEFAULT_PAWN_TABLE_MEGABYTES))
+    }
+}
+
+impl Evaluator for HandcraftedEvaluator {
+    fn evaluate(&mut self, board: &Board, color: PieceColor) -> i32 {
+        evaluate_with(board, color, &mut self.pawn_table)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MaterialEvaluator;
+
+impl Evaluator for MaterialEvaluator {
+    fn evaluate(&mut self, board: &Board, color: PieceColor) -> i32 {
+        Position::all()
+            .iter()
+            .filter_map(|position| board.piece(*position))
+            .filter(|piece| piece.kind() != PieceKind::King)
+            .map(|piece| {
+                if piece.color() == color {
+                    piece_value(piece.kind())
+                } else {
+                    -piece_value(piece.kind())
+                }
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Evaluator, HandcraftedEvaluator, MaterialEvaluator};
+    use crate::{evaluate, Searcher};
+    use chess::{board_move, fen, Board, PieceColor};
+
+    #[test]
+    fn builtin_evaluators() {
+        let board = fen!("4k3/8/8/3q4/8/8/4P3/3RK3 w - - 0 1");
+        assert_eq!(
+            HandcraftedEvaluator::default().evaluate(&board, PieceColor::White),
+            evaluate(&board, PieceColor::White)
+        );
+        let material = MaterialEvaluator.evaluate(&board, PieceColor::White);
+        assert!(material < 0);
+        assert_eq!(
+            material,
+            -MaterialEvaluator.evaluate(&board, PieceColor::Black)
+        );
+        assert_eq!(
+            MaterialEvaluator.evaluate(&Board::new(), PieceColor::White),
+            0
+        );
+    }
+
+    #[test]
+    fn search_uses_custom_evaluator() {
+        let board = fen!("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+        let result =
+            Searcher::with_evaluator(&board, PieceColor::White, MaterialEvaluator).search(2);
+        assert_eq!(result.best_move, Some(board_move!(d1 d5)));
+        let mut calls = 0;
+        let result = Searcher::with_evaluator(&board, PieceColor::White, |_: &Board, _| {
+            calls += 1;
+            0
+        })
+        .search(2);
+        assert_eq!(result.score, 0);
+        assert!(calls > 0);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{Evaluator, SearchResult, Searcher};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+#[cfg(target_arch = "wasm32")]
+const POLL_NODES: u64 = 10_000;
+
+struct SearchTask<E> {
+    searcher: Searcher<E>,
+    result: SearchResult,
+    depth: u32,
+    finished: bool,
+}
+
+impl<E: Evaluator> SearchTask<E> {
+    fn new(mut searcher: Searcher<E>, depth: u32) -> Self {
+        searcher.start();
+        Self {
+            searcher,
+            result: SearchResult::default(),
+            depth: depth.max(1),
+            finished: false,
+        }
+    }
+
+    fn step(&mut self, slice: Option<u64>) -> bool {
+        if !self.finished {
+            self.searcher.pause_after(slice);
+            self.finished =
+                !self.searcher.deepen(&mut self.result) || self.result.depth >= self.depth;
+        }
+        !self.finished
+    }
+}
+
+pub struct SearchHandle {
+    stop_flag: Arc<AtomicBool>,
+    progress: Arc<Mutex<SearchResult>>,
+    finished: Arc<AtomicBool>,
+    #[cfg(not(target_arch = "wasm32"))]
+    thread: Option<std::thread::JoinHandle<SearchResult>>,
+    #[cfg(target_arch = "wasm32")]
+    task: Box<dyn FnMut(Option<u64>) -> (bool, SearchResult) + Send + Sync>,
+}
+
+impl SearchHandle {
+    pub fn spawn<E: Evaluator + Send + Sync + 'static>(searcher: Searcher<E>, depth: u32) -> Self {
+        let stop_flag = Arc::new(AtomicBool::new(false));
+        let progress = Arc::new(Mutex::new(SearchResult::default()));
+        let finished = Arc::new(AtomicBool::new(false));
+        let mut task = SearchTask::new(searcher.stop_flag(stop_flag.clone()), depth);
+        #[cfg(not(target_arch = "wasm32"))]
+        let thread = {
+            let progress = progress.clone();
+            let finished = finished.clone();
+            Some(std::thread::spawn(move || {
+                while task.step(None) {
+                    *progress.lock().unwrap() = task.result.clone();
+                }
+                *progress.lock().unwrap() = task.result.clone();
+                finished.store(true, Ordering::Release);
+                task.result
+            }))
+        };
+        Self {
+            stop_flag,
+            progress,
+            finished,
+            #[cfg(not(target_arch = "wasm32"))]
+            thread,
+            #[cfg(target_arch = "wasm32")]
+            task: Box::new(move |slice| {
+                task.step(slice);
+                (task.finished, task.result.clone())
+            }),
+        }
+    }
+
+    pub fn poll(&mut self) -> SearchResult {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let (finished, result) = (self.task)(Some(POLL_NODES));
+            *self.progress.lock().unwrap() = result;
+            self.finished.store(finished, Ordering::Release);
+        }
+        self.progress()
+    }
+
+    pub fn progress(&self) -> SearchResult {
+        self.progress.lock().unwrap().clone()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+
+    pub fn stop(&self) {
+        self.stop_flag.store(true, Ordering::Relaxed);
+    }
+
+    pub fn wait(mut self) -> SearchResult {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            match self.thread.take().map(|thread| thread.join()) {
+                Some(Ok(result)) => result,
+                _ => self.progress(),
+            }
+        }
+        // Without threads there is nothing to wait for, so the rest of the
+        // search runs here in one go.
+        #[cfg(target_arch = "wasm32")]
+        loop {
+            let (finished, result) = (self.task)(None);
+            if finished {
+                break result;
+            }
+        }
+    }
+}
+
+impl Drop for SearchHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchHandle, SearchTask};
+    use crate::{AiConfig, Searcher, MAX_PLY};
+    use chess::{board_move, fen, Board, PieceColor};
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn background_search_matches_blocking_search() {
+        let board = fen!("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+        let mut handle = SearchHandle::spawn(Searcher::new(&board, PieceColor::White), 3);
+        let result = Searcher::new(&board, PieceColor::White).search(3);
+        while !handle.is_finished() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(handle.poll(), result);
+        assert_eq!(handle.wait().best_move, Some(board_move!(d1 d5)));
+    }
+
+    #[test]
+    fn sliced_search_resumes_where_it_paused() {
+        let board = fen!("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+        let mut task = SearchTask::new(Searcher::new(&board, PieceColor::White), 5);
+        let mut steps = 0;
+        while task.step(Some(100)) {
+            steps += 1;
+        }
+        assert!(steps > 5);
+        assert_eq!(task.result.depth, 5);
+        assert_eq!(task.result.best_move, Some(board_move!(d1 d5)));
+    }
+
+    #[test]
+    fn seeded_searches_repeat_their_moves() {
+        let board = Board::new();
+        let config = AiConfig {
+            depth: MAX_PLY,
+            node_limit: Some(2_000),
+            eval_noise: 20,
+            ..AiConfig::default()
+        };
+        let play = |seed| {
+            let searcher =
+                config.searcher(&board, PieceColor::White, &mut StdRng::seed_from_u64(seed));
+            SearchHandle::spawn(searcher, config.depth).wait().best_move
+        };
+        assert_eq!(play(3), play(3));
+        assert!((0..10).any(|seed| play(seed) != play(3)));
+    }
+
+    #[test]
+    fn stop_cancels_search() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let handle = SearchHandle::spawn(Searcher::new(&board, PieceColor::White), 64);
+        let start = Instant::now();
+        while handle.progress().depth < 1 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        handle.stop();
+        let result = handle.wait();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(result.depth < 64);
+        assert!(board.is_valid_move(result.best_move.unwrap()));
+    }
+}
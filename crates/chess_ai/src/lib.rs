@@ -1,64 +1,62 @@
 use chess::{Board, BoardMove, PieceColor};
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
 
-struct ScoredMove {
-    board: Board,
-    board_move: BoardMove,
-    score: i8,
+pub use analysis::{analyze, analyze_with_rng, MoveScore, ScoredMove};
+pub use bench::{bench, BenchReport, BENCH_POSITIONS};
+pub use config::{AiConfig, Difficulty};
+pub use epd::{run_epd, run_suite, EpdReport, EpdResult, SearchLimit};
+pub use eval::{evaluate, evaluate_with};
+pub use evaluator::{Evaluator, HandcraftedEvaluator, MaterialEvaluator};
+pub use handle::SearchHandle;
+pub use pawns::PawnTable;
+pub use search::{is_mate_score, SearchFeatures, SearchResult, Searcher, MATE_SCORE, MAX_PLY};
+pub use time::TimeControl;
+pub use tt::{Bound, TranspositionTable, TtEntry};
+
+mod analysis;
+mod bench;
+mod config;
+mod epd;
+mod eval;
+mod evaluator;
+mod handle;
+mod ordering;
+mod pawns;
+mod search;
+mod time;
+mod tt;
+
+const DEFAULT_DEPTH: u32 = 5;
+
+pub fn plan_move(board: &Board, color: PieceColor) -> Option<BoardMove> {
+    plan_move_with_config(board, color, &AiConfig::default())
 }
 
-fn plan_move_depth(board: &Board, color: PieceColor, depth: u32) -> Vec<ScoredMove> {
-    let mut board_moves = board.valid_moves(color);
-    let mut scored_moves = vec![];
-    let mut highest_score = -100;
-    while let Some(board_move) = board_moves.pop() {
-        let mut board = board.clone();
-        board.force_move(board_move);
-        let mut follow_up_score = 0;
-        if depth > 0 {
-            for moves in plan_move_depth(&board, color, 0) {
-                let mut board = board.clone();
-                board.force_move(moves.board_move);
-                let score = board.score(color);
-                if score > follow_up_score {
-                    follow_up_score = score;
-                }
-            }
-        }
-        let mut score = board.score(color);
-        if depth > 0 {
-            let responses = plan_move_depth(&board, color.opposite(), depth - 1);
-            for response in responses.iter() {
-                let res_score = response.board.score(color);
-                if response.board.score(color) < score {
-                    score = res_score;
-                }
-            }
-        }
-        score += follow_up_score / 5;
-        scored_moves.push(ScoredMove {
-            board,
-            board_move,
-            score,
-        });
-        if score > highest_score {
-            highest_score = score;
-        }
-    }
-    while rand::random::<f32>() < 0.25 {
-        highest_score -= 2;
-    }
-    scored_moves = scored_moves
-        .into_iter()
-        .filter(|board| board.score >= highest_score)
-        .collect();
-    scored_moves
+pub fn plan_move_with_config(
+    board: &Board,
+    color: PieceColor,
+    config: &AiConfig,
+) -> Option<BoardMove> {
+    plan_move_with_rng(board, color, config, &mut rand::thread_rng())
 }
 
-pub fn plan_move(board: &Board, color: PieceColor) -> Option<BoardMove> {
-    let moves = plan_move_depth(board, color, 2);
-    if !moves.is_empty() {
-        Some(moves[rand::random::<usize>() % moves.len()].board_move)
-    } else {
-        None
+pub fn plan_move_with_rng<R: Rng + ?Sized>(
+    board: &Board,
+    color: PieceColor,
+    config: &AiConfig,
+    rng: &mut R,
+) -> Option<BoardMove> {
+    let mut searcher = config.searcher(board, color, rng);
+    if config.temperature == 0 {
+        return searcher.search(config.depth).best_move;
     }
+    let scores = searcher.root_scores(config.depth);
+    let Some(best) = scores.iter().map(|(_, score)| *score).max() else {
+        return searcher.search(config.depth).best_move;
+    };
+    let weights = scores
+        .iter()
+        .map(|(_, score)| ((score - best) as f64 / config.temperature as f64).exp());
+    let index = WeightedIndex::new(weights).ok()?.sample(rng);
+    Some(scores[index].0)
 }
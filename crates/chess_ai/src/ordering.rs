@@ -0,0 +1,197 @@
+use crate::{eval::piece_value, search::MAX_PLY};
+use chess::{Board, BoardMove, PieceColor, PieceKind, SQUARE_COUNT};
+
+const HASH_MOVE: i32 = 1 << 30;
+const CAPTURE: i32 = 1 << 28;
+const KILLER: i32 = 1 << 27;
+const COUNTERMOVE: i32 = 1 << 26;
+const LOSING_CAPTURE: i32 = 1 << 25;
+const HISTORY_LIMIT: i32 = 1 << 20;
+
+pub(crate) struct MoveOrdering {
+    killers: Vec<[Option<BoardMove>; 2]>,
+    history: Vec<[i32; SQUARE_COUNT * SQUARE_COUNT]>,
+    countermoves: Vec<Option<BoardMove>>,
+}
+
+impl MoveOrdering {
+    pub(crate) fn new() -> Self {
+        Self {
+            killers: vec![[None; 2]; MAX_PLY as usize + 1],
+            history: vec![[0; SQUARE_COUNT * SQUARE_COUNT]; 2],
+            countermoves: vec![None; SQUARE_COUNT * SQUARE_COUNT],
+        }
+    }
+
+    pub(crate) fn order(
+        &self,
+        board: &Board,
+        moves: &mut [BoardMove],
+        hash_move: Option<BoardMove>,
+        previous_move: Option<BoardMove>,
+        ply: u32,
+    ) {
+        let killers = self.killers[ply as usize];
+        let countermove =
+            previous_move.and_then(|previous_move| self.countermoves[index(previous_move)]);
+        moves.sort_by_cached_key(|board_move| {
+            let key = if Some(*board_move) == hash_move {
+                HASH_MOVE
+            } else if let Some(score) = mvv_lva(board, *board_move) {
+                match losing_capture(board, *board_move) {
+                    Some(see) => LOSING_CAPTURE + see,
+                    None => CAPTURE + score,
+                }
+            } else if Some(*board_move) == killers[0] {
+                KILLER + 1
+            } else if Some(*board_move) == killers[1] {
+                KILLER
+            } else if Some(*board_move) == countermove {
+                COUNTERMOVE
+            } else {
+                self.history(board.turn(), *board_move)
+            };
+            -key
+        });
+    }
+
+    pub(crate) fn cutoff(
+        &mut self,
+        board: &Board,
+        board_move: BoardMove,
+        previous_move: Option<BoardMove>,
+        ply: u32,
+        depth: u32,
+    ) {
+        if mvv_lva(board, board_move).is_some() {
+            return;
+        }
+        let killers = &mut self.killers[ply as usize];
+        if killers[0] != Some(board_move) {
+            killers[1] = killers[0];
+            killers[0] = Some(board_move);
+        }
+        if let Some(previous_move) = previous_move {
+            self.countermoves[index(previous_move)] = Some(board_move);
+        }
+        let history = &mut self.history[board.turn() as usize];
+        history[index(board_move)] += (depth * depth) as i32;
+        if history[index(board_move)] >= HISTORY_LIMIT {
+            history.iter_mut().for_each(|score| *score /= 2);
+        }
+    }
+
+    pub(crate) fn history(&self, color: PieceColor, board_move: BoardMove) -> i32 {
+        self.history[color as usize][index(board_move)]
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.killers.fill([None; 2]);
+        self.history.iter_mut().for_each(|history| history.fill(0));
+        self.countermoves.fill(None);
+    }
+}
+
+fn index(board_move: BoardMove) -> usize {
+    board_move.from.index() * SQUARE_COUNT + board_move.to.index()
+}
+
+// The exchange value of a capture that loses material. `Board::see` counts a
+// promoting pawn as a pawn, so promotions are left to MVV-LVA.
+pub(crate) fn losing_capture(board: &Board, board_move: BoardMove) -> Option<i32> {
+    let piece = board.piece(board_move.from)?;
+    let promotion = piece.kind() == PieceKind::Pawn
+        && board_move.to.row() == piece.color().opposite().home_row();
+    if promotion || board.piece(board_move.to).is_none() {
+        return None;
+    }
+    Some(board.see(board_move)).filter(|see| *see < 0)
+}
+
+pub(crate) fn mvv_lva(board: &Board, board_move: BoardMove) -> Option<i32> {
+    let piece = board.piece(board_move.from)?;
+    let victim = match board.piece(board_move.to) {
+        Some(captured) => Some(captured.kind()),
+        None if piece.kind() == PieceKind::Pawn && board_move.from.col() != board_move.to.col() => {
+            Some(PieceKind::Pawn)
+        }
+        None => None,
+    };
+    let promotion = piece.kind() == PieceKind::Pawn
+        && board_move.to.row() == piece.color().opposite().home_row();
+    if victim.is_none() && !promotion {
+        return None;
+    }
+    let mut score = victim.map_or(0, |victim| {
+        piece_value(victim) * 8 - piece_value(piece.kind())
+    });
+    if promotion {
+        score += piece_value(PieceKind::Queen);
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{losing_capture, mvv_lva, MoveOrdering};
+    use chess::{board_move, fen, PieceColor};
+
+    #[test]
+    fn captures_are_ordered_by_mvv_lva() {
+        let board = fen!("4k3/8/8/2q1r3/3P4/8/8/3Q3K w - - 0 1");
+        let mut moves = board.valid_moves(PieceColor::White);
+        MoveOrdering::new().order(&board, &mut moves, None, None, 0);
+        assert_eq!(&moves[..2], [board_move!(d4 c5), board_move!(d4 e5)]);
+        assert!(mvv_lva(&board, board_move!(d4 c5)) > mvv_lva(&board, board_move!(d4 e5)));
+        assert_eq!(mvv_lva(&board, board_move!(d1 d2)), None);
+        let mut moves = board.valid_moves(PieceColor::White);
+        MoveOrdering::new().order(&board, &mut moves, Some(board_move!(d1 d3)), None, 0);
+        assert_eq!(moves[0], board_move!(d1 d3));
+    }
+
+    #[test]
+    fn losing_captures_follow_killers() {
+        let board = fen!("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1");
+        assert_eq!(losing_capture(&board, board_move!(d1 d5)), Some(-7));
+        let mut ordering = MoveOrdering::new();
+        ordering.cutoff(&board, board_move!(d1 a4), None, 0, 1);
+        let mut moves = board.valid_moves(PieceColor::White);
+        ordering.order(&board, &mut moves, None, None, 0);
+        assert_eq!(&moves[..2], [board_move!(d1 a4), board_move!(d1 d5)]);
+        let mut moves = board.valid_moves(PieceColor::White);
+        MoveOrdering::new().order(&board, &mut moves, None, None, 0);
+        assert_eq!(moves[0], board_move!(d1 d5));
+        let board = fen!("7k/8/1pp5/n2r4/8/8/8/R2Q3K w - - 0 1");
+        assert!(mvv_lva(&board, board_move!(d1 d5)) > mvv_lva(&board, board_move!(a1 a5)));
+        let mut moves = board.valid_moves(PieceColor::White);
+        MoveOrdering::new().order(&board, &mut moves, None, None, 0);
+        assert_eq!(&moves[..2], [board_move!(a1 a5), board_move!(d1 d5)]);
+    }
+
+    #[test]
+    fn killers_countermoves_and_history() {
+        let board = fen!("4k3/8/8/2q1r3/3P4/8/8/3Q3K w - - 0 1");
+        let mut ordering = MoveOrdering::new();
+        let previous = board_move!(e8 f8);
+        ordering.cutoff(&board, board_move!(d1 a4), Some(previous), 3, 4);
+        ordering.cutoff(&board, board_move!(d1 b3), None, 3, 2);
+        ordering.cutoff(&board, board_move!(d4 c5), None, 3, 2);
+        assert_eq!(ordering.history(PieceColor::White, board_move!(d1 a4)), 16);
+        let mut moves = board.valid_moves(PieceColor::White);
+        ordering.order(&board, &mut moves, None, None, 3);
+        assert_eq!(
+            &moves[..4],
+            [
+                board_move!(d4 c5),
+                board_move!(d4 e5),
+                board_move!(d1 b3),
+                board_move!(d1 a4)
+            ]
+        );
+        let mut moves = board.valid_moves(PieceColor::White);
+        ordering.order(&board, &mut moves, None, Some(previous), 5);
+        assert_eq!(moves[2], board_move!(d1 a4));
+        ordering.clear();
+        assert_eq!(ordering.history(PieceColor::White, board_move!(d1 a4)), 0);
+    }
+}
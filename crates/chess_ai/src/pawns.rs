@@ -0,0 +1,280 @@
+use crate::eval::Score;
+use chess::{Board, Ic, Piece, PieceColor, PieceKind, Position, PositionOffset};
+use std::mem::size_of;
+
+const PASSED: [Score; 8] = [
+    Score(0, 0),
+    Score(5, 10),
+    Score(10, 20),
+    Score(20, 40),
+    Score(35, 70),
+    Score(60, 120),
+    Score(100, 200),
+    Score(0, 0),
+];
+const CANDIDATE: [Score; 8] = [
+    Score(0, 0),
+    Score(2, 5),
+    Score(4, 10),
+    Score(8, 18),
+    Score(14, 30),
+    Score(24, 50),
+    Score(0, 0),
+    Score(0, 0),
+];
+const DOUBLED: Score = Score(-10, -20);
+const ISOLATED: Score = Score(-12, -15);
+const BACKWARD: Score = Score(-8, -10);
+const CHAIN: Score = Score(8, 10);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct PawnEntry {
+    key: u64,
+    scores: [Score; 2],
+    passed: [u64; 2],
+}
+
+impl PawnEntry {
+    pub(crate) fn score(&self, color: PieceColor) -> Score {
+        self.scores[color as usize]
+    }
+
+    pub(crate) fn passed(&self, color: PieceColor) -> impl Iterator<Item = Position> + '_ {
+        let passed = self.passed[color as usize];
+        Position::all()
+            .iter()
+            .copied()
+            .filter(move |position| passed & (1 << position.index()) != 0)
+    }
+}
+
+pub struct PawnTable {
+    entries: Vec<Option<PawnEntry>>,
+}
+
+impl PawnTable {
+    pub fn new(megabytes: usize) -> Self {
+        let len = megabytes * 1024 * 1024 / size_of::<Option<PawnEntry>>();
+        Self {
+            entries: vec![None; len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+    }
+
+    pub(crate) fn probe(&mut self, board: &Board) -> PawnEntry {
+        let key = board.pawn_hash();
+        if self.entries.is_empty() {
+            return analyze(board, key);
+        }
+        let index = (key % self.entries.len() as u64) as usize;
+        match self.entries[index] {
+            Some(entry) if entry.key == key => entry,
+            _ => {
+                let entry = analyze(board, key);
+                self.entries[index] = Some(entry);
+                entry
+            }
+        }
+    }
+
+    pub fn contains(&self, board: &Board) -> bool {
+        let key = board.pawn_hash();
+        !self.entries.is_empty()
+            && self.entries[(key % self.entries.len() as u64) as usize]
+                .is_some_and(|entry| entry.key == key)
+    }
+}
+
+pub(crate) fn passed_path(board: &Board, entry: &PawnEntry, color: PieceColor) -> Score {
+    let mut score = Score::default();
+    for position in entry.passed(color) {
+        let forward = PositionOffset(0, color.forward());
+        let mut next = position.offset(forward);
+        let mut free = true;
+        while let Some(square) = next {
+            free &= board.piece(square).is_none() && !board.is_attacked(square, color.opposite());
+            next = square.offset(forward);
+        }
+        if free {
+            score.1 += PASSED[position.relative_row(color) as usize].1 / 2;
+        }
+    }
+    score
+}
+
+fn is_pawn(board: &Board, col: Ic, row: Ic, color: PieceColor) -> bool {
+    Position(0, 0)
+        .offset(PositionOffset(col, row))
+        .and_then(|position| board.piece(position))
+        == Some(Piece::new(PieceKind::Pawn, color))
+}
+
+fn count_pawns(
+    board: &Board,
+    col: Ic,
+    color: PieceColor,
+    mut filter: impl FnMut(Ic) -> bool,
+) -> usize {
+    (0..Position::row_count() as Ic)
+        .filter(|row| filter(*row) && is_pawn(board, col, *row, color))
+        .count()
+}
+
+pub(crate) fn analyze(board: &Board, key: u64) -> PawnEntry {
+    let mut entry = PawnEntry {
+        key,
+        scores: [Score::default(); 2],
+        passed: [0; 2],
+    };
+    for color in [PieceColor::White, PieceColor::Black] {
+        let enemy = color.opposite();
+        let forward = color.forward();
+        let mut score = Score::default();
+        for col in 0..Position::col_count() as Ic {
+            let count = count_pawns(board, col, color, |_| true);
+            if count > 1 {
+                score += DOUBLED * (count as i32 - 1);
+            }
+        }
+        for position in Position::all().iter() {
+            if board.piece(*position) != Some(Piece::new(PieceKind::Pawn, color)) {
+                continue;
+            }
+            let col = position.col() as Ic;
+            let row = position.row() as Ic;
+            let ahead = |other: Ic| (other - row) * forward > 0;
+            let relative_row = position.relative_row(color) as usize;
+            let adjacent = [col - 1, col + 1];
+            let front = count_pawns(board, col, color, ahead) == 0;
+            let blockers = count_pawns(board, col, enemy, ahead);
+            let sentries: usize = adjacent
+                .iter()
+                .map(|col| count_pawns(board, *col, enemy, ahead))
+                .sum();
+            let neighbours: usize = adjacent
+                .iter()
+                .map(|col| count_pawns(board, *col, color, |_| true))
+                .sum();
+            let supporters: usize = adjacent
+                .iter()
+                .map(|col| count_pawns(board, *col, color, |other| !ahead(other)))
+                .sum();
+            if front && blockers == 0 && sentries == 0 {
+                score += PASSED[relative_row];
+                entry.passed[color as usize] |= 1 << position.index();
+            } else if front && blockers == 0 && supporters >= sentries {
+                score += CANDIDATE[relative_row];
+            }
+            if neighbours == 0 {
+                score += ISOLATED;
+            } else if supporters == 0
+                && adjacent
+                    .iter()
+                    .any(|col| is_pawn(board, *col, row + forward * 2, enemy))
+            {
+                score += BACKWARD;
+            }
+            if adjacent
+                .iter()
+                .any(|col| is_pawn(board, *col, row - forward, color))
+            {
+                score += CHAIN;
+            }
+        }
+        entry.scores[color as usize] = score;
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze, passed_path, PawnTable, BACKWARD, CHAIN, DOUBLED, ISOLATED, PASSED};
+    use crate::eval::{evaluate, Score};
+    use chess::{fen, Board, PieceColor, Position};
+
+    fn score(board: &Board, color: PieceColor) -> Score {
+        analyze(board, board.pawn_hash()).score(color)
+    }
+
+    #[test]
+    fn passed_pawns_scale_with_rank() {
+        let board = fen!("4k3/8/8/8/8/8/P7/4K3 w - - 0 1");
+        assert_eq!(score(&board, PieceColor::White), PASSED[1] + ISOLATED);
+        let entry = analyze(&board, board.pawn_hash());
+        assert_eq!(
+            entry.passed(PieceColor::White).collect::<Vec<_>>(),
+            vec![Position::from_name("a2").unwrap()]
+        );
+        let advanced = fen!("4k3/P7/8/8/8/8/8/4K3 w - - 0 1");
+        assert!(evaluate(&advanced, PieceColor::White) > evaluate(&board, PieceColor::White) + 100);
+        let blocked = fen!("4k3/8/1p6/8/8/8/P7/4K3 w - - 0 1");
+        assert_eq!(analyze(&blocked, 0).passed(PieceColor::White).count(), 0);
+        assert_eq!(analyze(&blocked, 0).passed(PieceColor::Black).count(), 0);
+    }
+
+    #[test]
+    fn passed_path_must_be_empty_and_unattacked() {
+        let path = |board: &Board| {
+            passed_path(board, &analyze(board, board.pawn_hash()), PieceColor::White)
+        };
+        assert_eq!(
+            path(&fen!("4k3/8/P7/8/8/8/8/4K3 w - - 0 1")),
+            Score(0, PASSED[5].1 / 2)
+        );
+        assert_eq!(
+            path(&fen!("1r2k3/8/P7/8/8/8/8/4K3 w - - 0 1")),
+            Score::default()
+        );
+        assert_eq!(
+            path(&fen!("n3k3/8/P7/8/8/8/8/4K3 w - - 0 1")),
+            Score::default()
+        );
+    }
+
+    #[test]
+    fn structural_weaknesses() {
+        let doubled = fen!("4k3/8/8/8/8/P7/P7/4K3 w - - 0 1");
+        assert_eq!(
+            score(&doubled, PieceColor::White),
+            DOUBLED + ISOLATED * 2 + PASSED[2]
+        );
+        let entry = analyze(&doubled, doubled.pawn_hash());
+        assert_eq!(
+            entry.passed(PieceColor::White).collect::<Vec<_>>(),
+            vec![Position::from_name("a3").unwrap()]
+        );
+        let chain = fen!("4k3/8/8/8/8/2P5/1P6/4K3 w - - 0 1");
+        assert_eq!(
+            score(&chain, PieceColor::White),
+            PASSED[1] + PASSED[2] + CHAIN
+        );
+        let backward = fen!("4k3/8/8/1P1p4/8/2P5/8/4K3 w - - 0 1");
+        assert_eq!(score(&backward, PieceColor::White), BACKWARD + PASSED[4]);
+    }
+
+    #[test]
+    fn table_caches_by_pawn_placement() {
+        let mut table = PawnTable::new(1);
+        let board = fen!("r3k2r/pp3ppp/8/3p4/3P4/8/PP3PPP/R3K2R w KQkq - 0 1");
+        assert!(!table.contains(&board));
+        let entry = table.probe(&board);
+        assert!(table.contains(&board));
+        let moved = fen!("4k3/pp3ppp/8/3p4/3P4/8/PP3PPP/4K3 b - - 0 1");
+        assert!(table.contains(&moved));
+        assert_eq!(table.probe(&moved), entry);
+        table.clear();
+        assert!(!table.contains(&board));
+        assert!(PawnTable::new(0).is_empty());
+    }
+}
@@ -0,0 +1,906 @@
+use crate::{
+    eval::piece_value,
+    evaluator::{Evaluator, HandcraftedEvaluator},
+    ordering::{losing_capture, mvv_lva, MoveOrdering},
+    time::TimeControl,
+    tt::{score_from_tt, score_to_tt, Bound, TranspositionTable, TtEntry},
+};
+use chess::{Board, BoardMove, PieceColor, PieceKind, Position};
+use instant::Instant;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+pub const MATE_SCORE: i32 = 30_000;
+pub const MAX_PLY: u32 = 128;
+const INFINITY: i32 = MATE_SCORE + 1;
+const DEFAULT_TT_MEGABYTES: usize = 16;
+const DELTA_MARGIN: i32 = 200;
+const TIME_CHECK_NODES: u64 = 1024;
+const NULL_MOVE_MIN_DEPTH: u32 = 3;
+const NULL_MOVE_REDUCTION: u32 = 2;
+const REVERSE_FUTILITY_DEPTH: u32 = 3;
+const REVERSE_FUTILITY_MARGIN: i32 = 120;
+const FUTILITY_DEPTH: u32 = 2;
+const FUTILITY_MARGIN: i32 = 150;
+const LMR_MIN_DEPTH: u32 = 3;
+const LMR_MIN_INDEX: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchFeatures {
+    pub null_move: bool,
+    pub late_move_reductions: bool,
+    pub futility_pruning: bool,
+    pub reverse_futility_pruning: bool,
+    pub check_extensions: bool,
+}
+
+impl SearchFeatures {
+    pub fn none() -> Self {
+        Self {
+            null_move: false,
+            late_move_reductions: false,
+            futility_pruning: false,
+            reverse_futility_pruning: false,
+            check_extensions: false,
+        }
+    }
+}
+
+impl Default for SearchFeatures {
+    fn default() -> Self {
+        Self {
+            null_move: true,
+            late_move_reductions: true,
+            futility_pruning: true,
+            reverse_futility_pruning: true,
+            check_extensions: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchResult {
+    pub best_move: Option<BoardMove>,
+    pub score: i32,
+    pub depth: u32,
+    pub pv: Vec<BoardMove>,
+    pub nodes: u64,
+}
+
+pub fn is_mate_score(score: i32) -> bool {
+    score.abs() >= MATE_SCORE - MAX_PLY as i32
+}
+
+pub struct Searcher<E = HandcraftedEvaluator> {
+    board: Board,
+    color: PieceColor,
+    nodes: u64,
+    pv: Vec<BoardMove>,
+    table: TranspositionTable,
+    evaluator: E,
+    check_evasions: bool,
+    ordering: MoveOrdering,
+    move_ordering: bool,
+    move_stack: Vec<Option<BoardMove>>,
+    path: Vec<u64>,
+    features: SearchFeatures,
+    root_depth: u32,
+    node_limit: Option<u64>,
+    time_control: Option<TimeControl>,
+    budget: Option<(Instant, Duration)>,
+    stopped: bool,
+    stop_flag: Option<Arc<AtomicBool>>,
+    pause_at: Option<u64>,
+    paused: bool,
+    excluded: Vec<BoardMove>,
+    eval_noise: i32,
+    noise_seed: u64,
+}
+
+impl Searcher {
+    pub fn new(board: &Board, color: PieceColor) -> Self {
+        Self::with_table(board, color, TranspositionTable::new(DEFAULT_TT_MEGABYTES))
+    }
+
+    pub fn with_table(board: &Board, color: PieceColor, table: TranspositionTable) -> Self {
+        Self::from_parts(board, color, table, HandcraftedEvaluator::default())
+    }
+}
+
+impl<E: Evaluator> Searcher<E> {
+    pub fn with_evaluator(board: &Board, color: PieceColor, evaluator: E) -> Self {
+        Self::from_parts(
+            board,
+            color,
+            TranspositionTable::new(DEFAULT_TT_MEGABYTES),
+            evaluator,
+        )
+    }
+
+    fn from_parts(
+        board: &Board,
+        color: PieceColor,
+        table: TranspositionTable,
+        evaluator: E,
+    ) -> Self {
+        // Searching the side that isn't to move plays on as if the other side
+        // had passed.
+        let mut board = *board;
+        if board.turn() != color {
+            board.null_move();
+        }
+        Self {
+            board,
+            color,
+            nodes: 0,
+            pv: vec![],
+            table,
+            evaluator,
+            check_evasions: true,
+            ordering: MoveOrdering::new(),
+            move_ordering: true,
+            move_stack: vec![],
+            path: vec![],
+            features: SearchFeatures::default(),
+            root_depth: 0,
+            node_limit: None,
+            time_control: None,
+            budget: None,
+            stopped: false,
+            stop_flag: None,
+            pause_at: None,
+            paused: false,
+            excluded: vec![],
+            eval_noise: 0,
+            noise_seed: 0,
+        }
+    }
+
+    pub fn tt_size(mut self, megabytes: usize) -> Self {
+        self.table = TranspositionTable::new(megabytes);
+        self
+    }
+
+    pub fn check_evasions(mut self, check_evasions: bool) -> Self {
+        self.check_evasions = check_evasions;
+        self
+    }
+
+    pub fn move_ordering(mut self, move_ordering: bool) -> Self {
+        self.move_ordering = move_ordering;
+        self
+    }
+
+    pub fn features(mut self, features: SearchFeatures) -> Self {
+        self.features = features;
+        self
+    }
+
+    pub fn node_limit(mut self, node_limit: Option<u64>) -> Self {
+        self.node_limit = node_limit;
+        self
+    }
+
+    pub fn time_control(mut self, time_control: Option<TimeControl>) -> Self {
+        self.time_control = time_control;
+        self
+    }
+
+    pub fn stop_flag(mut self, stop_flag: Arc<AtomicBool>) -> Self {
+        self.stop_flag = Some(stop_flag);
+        self
+    }
+
+    pub fn eval_noise(mut self, eval_noise: i32, seed: u64) -> Self {
+        self.eval_noise = eval_noise;
+        self.noise_seed = seed;
+        self
+    }
+
+    pub fn into_table(self) -> TranspositionTable {
+        self.table
+    }
+
+    pub fn search(&mut self, depth: u32) -> SearchResult {
+        self.start();
+        let mut result = SearchResult::default();
+        while self.deepen(&mut result) && result.depth < depth.max(1) {}
+        result
+    }
+
+    pub(crate) fn deepen(&mut self, result: &mut SearchResult) -> bool {
+        let iteration = self.iterate(result.depth + 1);
+        // A paused iteration is retried from the same depth on the next call;
+        // the subtrees it finished are already in the table.
+        if self.paused {
+            self.paused = false;
+            self.stopped = false;
+            self.pv = result.pv.clone();
+            result.nodes = self.nodes;
+            return true;
+        }
+        if self.stopped {
+            if result.best_move.is_none() {
+                *result = iteration;
+                result.best_move = result.best_move.or_else(|| {
+                    self.board
+                        .moves(self.color)
+                        .find(|board_move| !self.excluded.contains(board_move))
+                });
+            }
+            result.nodes = self.nodes;
+            return false;
+        }
+        *result = iteration;
+        !(result.best_move.is_none() || is_mate_score(result.score) || self.half_budget_used())
+    }
+
+    // Another iteration takes at least as long as all the previous ones.
+    pub(crate) fn half_budget_used(&self) -> bool {
+        self.budget
+            .is_some_and(|(start, budget)| start.elapsed() >= budget / 2)
+    }
+
+    // Scores every root move, deepening until `depth` or the budget runs out.
+    // Only completed iterations count, so the result is empty or covers every
+    // move.
+    pub fn root_scores(&mut self, depth: u32) -> Vec<(BoardMove, i32)> {
+        self.start();
+        let mut scores = vec![];
+        for depth in 1..=depth.max(1) {
+            let iteration = self.score_root(depth);
+            if self.stopped {
+                break;
+            }
+            scores = iteration;
+            if self.half_budget_used() {
+                break;
+            }
+        }
+        scores
+    }
+
+    fn score_root(&mut self, depth: u32) -> Vec<(BoardMove, i32)> {
+        let board = self.board;
+        let mut scores = vec![];
+        let mut pv = vec![];
+        self.path.push(board.hash());
+        for board_move in board.moves(self.color) {
+            let mut child = board;
+            child.force_move(board_move);
+            let score = -self.negamax(
+                &child,
+                self.color.opposite(),
+                depth - 1,
+                1,
+                -INFINITY,
+                INFINITY,
+                &[],
+                &mut pv,
+            );
+            if self.stopped {
+                break;
+            }
+            scores.push((board_move, score));
+        }
+        self.path.pop();
+        scores
+    }
+
+    // One iteration for a multi-PV line: the best root move outside
+    // `excluded`, starting from `pv`. The budget carries over from `start`.
+    pub(crate) fn search_line(
+        &mut self,
+        depth: u32,
+        excluded: Vec<BoardMove>,
+        pv: Vec<BoardMove>,
+    ) -> SearchResult {
+        self.excluded = excluded;
+        self.pv = pv;
+        self.iterate(depth)
+    }
+
+    pub(crate) fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    pub(crate) fn pause_after(&mut self, nodes: Option<u64>) {
+        self.pause_at = nodes.map(|nodes| self.nodes + nodes);
+    }
+
+    pub(crate) fn start(&mut self) {
+        self.nodes = 0;
+        self.ordering.clear();
+        self.move_stack.clear();
+        self.path.clear();
+        self.stopped = false;
+        self.paused = false;
+        self.budget = self
+            .time_control
+            .map(|time_control| (Instant::now(), time_control.budget()));
+    }
+
+    fn should_stop(&mut self) -> bool {
+        if !self.stopped {
+            self.stopped = self.node_limit.is_some_and(|limit| self.nodes >= limit)
+                || self
+                    .stop_flag
+                    .as_ref()
+                    .is_some_and(|stop_flag| stop_flag.load(Ordering::Relaxed))
+                || (self.nodes % TIME_CHECK_NODES == 0
+                    && self
+                        .budget
+                        .is_some_and(|(start, budget)| start.elapsed() >= budget));
+            self.paused = !self.stopped && self.pause_at.is_some_and(|at| self.nodes >= at);
+            self.stopped |= self.paused;
+        }
+        self.stopped
+    }
+
+    // Only positions since the last irreversible move with the same side to
+    // move can repeat.
+    fn is_repetition(&self, key: u64, halfmove_clock: u32) -> bool {
+        self.path
+            .iter()
+            .rev()
+            .take(halfmove_clock as usize)
+            .skip(1)
+            .step_by(2)
+            .any(|ancestor| *ancestor == key)
+    }
+
+    fn static_eval(&mut self, board: &Board, color: PieceColor) -> i32 {
+        self.evaluator.evaluate(board, color) + self.noise(board)
+    }
+
+    fn noise(&self, board: &Board) -> i32 {
+        if self.eval_noise <= 0 {
+            return 0;
+        }
+        let mut x = board.hash() ^ self.noise_seed;
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^= x >> 31;
+        (x % (self.eval_noise as u64 * 2 + 1)) as i32 - self.eval_noise
+    }
+
+    pub fn iterate(&mut self, depth: u32) -> SearchResult {
+        self.root_depth = depth;
+        let mut pv = vec![];
+        let previous_pv = std::mem::take(&mut self.pv);
+        let board = self.board;
+        let score = self.negamax(
+            &board,
+            self.color,
+            depth,
+            0,
+            -INFINITY,
+            INFINITY,
+            &previous_pv,
+            &mut pv,
+        );
+        self.pv = pv;
+        SearchResult {
+            best_move: self.pv.first().copied(),
+            score,
+            depth,
+            pv: self.pv.clone(),
+            nodes: self.nodes,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn negamax(
+        &mut self,
+        board: &Board,
+        color: PieceColor,
+        depth: u32,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+        previous_pv: &[BoardMove],
+        pv: &mut Vec<BoardMove>,
+    ) -> i32 {
+        self.nodes += 1;
+        pv.clear();
+        if self.should_stop() {
+            return 0;
+        }
+        let key = board.hash();
+        if ply > 0
+            && (board.halfmove_clock() >= 100
+                || board.is_insufficient_material()
+                || self.is_repetition(key, board.halfmove_clock()))
+        {
+            return 0;
+        }
+        let entry = self.table.probe(key);
+        if let Some(entry) = entry.filter(|entry| ply > 0 && entry.depth >= depth) {
+            let score = score_from_tt(entry.score, ply);
+            let cutoff = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => score >= beta,
+                Bound::Upper => score <= alpha,
+            };
+            if cutoff {
+                pv.extend(entry.best_move);
+                return score;
+            }
+        }
+        let in_check = board.check(color);
+        let depth = if in_check && self.features.check_extensions && ply < self.root_depth * 2 {
+            depth + 1
+        } else {
+            depth
+        };
+        if depth == 0 || ply >= MAX_PLY {
+            return self.quiesce(board, color, ply, alpha, beta);
+        }
+        let prunable = ply > 0 && !in_check && !is_mate_score(alpha) && !is_mate_score(beta);
+        let static_eval = if prunable {
+            self.static_eval(board, color)
+        } else {
+            -INFINITY
+        };
+        if prunable
+            && self.features.reverse_futility_pruning
+            && depth <= REVERSE_FUTILITY_DEPTH
+            && static_eval - REVERSE_FUTILITY_MARGIN * depth as i32 >= beta
+        {
+            return static_eval;
+        }
+        if prunable
+            && self.features.null_move
+            && depth >= NULL_MOVE_MIN_DEPTH
+            && static_eval >= beta
+            && self.move_stack.last() != Some(&None)
+            && has_pieces(board, color)
+        {
+            let mut child = *board;
+            child.null_move();
+            self.move_stack.push(None);
+            self.path.push(key);
+            let reduction = NULL_MOVE_REDUCTION + depth / 6;
+            let score = -self.negamax(
+                &child,
+                color.opposite(),
+                depth.saturating_sub(1 + reduction),
+                ply + 1,
+                -beta,
+                -beta + 1,
+                &[],
+                pv,
+            );
+            self.move_stack.pop();
+            self.path.pop();
+            pv.clear();
+            if self.stopped {
+                return 0;
+            }
+            if score >= beta {
+                return if is_mate_score(score) { beta } else { score };
+            }
+        }
+        let futile = prunable
+            && self.features.futility_pruning
+            && depth <= FUTILITY_DEPTH
+            && static_eval + FUTILITY_MARGIN * depth as i32 <= alpha;
+        let mut moves = board.valid_moves(color);
+        if moves.is_empty() {
+            return if in_check {
+                -MATE_SCORE + ply as i32
+            } else {
+                0
+            };
+        }
+        if ply == 0 {
+            moves.retain(|board_move| !self.excluded.contains(board_move));
+        }
+        let first_move = previous_pv
+            .first()
+            .copied()
+            .or(entry.and_then(|entry| entry.best_move));
+        let previous_move = self.move_stack.last().copied().flatten();
+        if self.move_ordering {
+            self.ordering
+                .order(board, &mut moves, first_move, previous_move, ply);
+        } else if let Some(index) = first_move.and_then(|first_move| {
+            moves
+                .iter()
+                .position(|board_move| *board_move == first_move)
+        }) {
+            moves[..=index].rotate_right(1);
+        }
+        let original_alpha = alpha;
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+        let mut child_pv = vec![];
+        for (index, board_move) in moves.iter().enumerate() {
+            let mut child = *board;
+            child.force_move(*board_move);
+            let quiet = mvv_lva(board, *board_move).is_none();
+            let gives_check = quiet && child.check(color.opposite());
+            if futile && quiet && !gives_check && best_move.is_some() {
+                continue;
+            }
+            let reduction = if self.features.late_move_reductions
+                && quiet
+                && !in_check
+                && !gives_check
+                && depth >= LMR_MIN_DEPTH
+                && index >= LMR_MIN_INDEX
+            {
+                if depth >= 6 && index >= LMR_MIN_INDEX * 2 {
+                    2
+                } else {
+                    1
+                }
+            } else {
+                0
+            };
+            let child_previous_pv = if previous_pv.first() == Some(board_move) {
+                &previous_pv[1..]
+            } else {
+                &[]
+            };
+            self.move_stack.push(Some(*board_move));
+            self.path.push(key);
+            let reduced = (reduction > 0).then(|| {
+                -self.negamax(
+                    &child,
+                    color.opposite(),
+                    depth - 1 - reduction,
+                    ply + 1,
+                    -alpha - 1,
+                    -alpha,
+                    &[],
+                    &mut child_pv,
+                )
+            });
+            let score = match reduced {
+                Some(score) if score <= alpha || self.stopped => score,
+                _ => -self.negamax(
+                    &child,
+                    color.opposite(),
+                    depth - 1,
+                    ply + 1,
+                    -beta,
+                    -alpha,
+                    child_previous_pv,
+                    &mut child_pv,
+                ),
+            };
+            self.move_stack.pop();
+            self.path.pop();
+            if self.stopped {
+                return 0;
+            }
+            if score > best_score {
+                best_score = score;
+                best_move = Some(*board_move);
+            }
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(*board_move);
+                pv.extend_from_slice(&child_pv);
+                if alpha >= beta {
+                    if self.move_ordering {
+                        self.ordering
+                            .cutoff(board, *board_move, previous_move, ply, depth);
+                    }
+                    break;
+                }
+            }
+        }
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        if ply > 0 || self.excluded.is_empty() {
+            self.table.store(TtEntry {
+                key,
+                depth,
+                bound,
+                score: score_to_tt(best_score, ply),
+                best_move,
+            });
+        }
+        best_score
+    }
+}
+
+impl<E: Evaluator> Searcher<E> {
+    fn quiesce(
+        &mut self,
+        board: &Board,
+        color: PieceColor,
+        ply: u32,
+        mut alpha: i32,
+        beta: i32,
+    ) -> i32 {
+        self.nodes += 1;
+        if self.should_stop() {
+            return 0;
+        }
+        let moves = board.valid_moves(color);
+        let in_check = board.check(color);
+        if moves.is_empty() {
+            return if in_check {
+                -MATE_SCORE + ply as i32
+            } else {
+                0
+            };
+        }
+        let evasions = in_check && self.check_evasions;
+        let stand_pat = self.static_eval(board, color);
+        if !evasions {
+            if stand_pat >= beta || ply >= MAX_PLY {
+                return stand_pat;
+            }
+            if stand_pat + piece_value(PieceKind::Queen) + DELTA_MARGIN < alpha {
+                return alpha;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+        let mut moves: Vec<(BoardMove, i32)> = moves
+            .into_iter()
+            .filter_map(|board_move| {
+                let gain = capture_gain(board, board_move);
+                (evasions || gain.is_some()).then(|| (board_move, gain.unwrap_or(0)))
+            })
+            .collect();
+        moves.sort_by_key(|(_, gain)| -gain);
+        let mut best_score = if evasions { -INFINITY } else { stand_pat };
+        for (board_move, gain) in moves {
+            if !evasions
+                && (stand_pat + gain + DELTA_MARGIN < alpha
+                    || losing_capture(board, board_move).is_some())
+            {
+                continue;
+            }
+            let mut child = *board;
+            child.force_move(board_move);
+            let score = -self.quiesce(&child, color.opposite(), ply + 1, -beta, -alpha);
+            if self.stopped {
+                return 0;
+            }
+            best_score = best_score.max(score);
+            if score > alpha {
+                alpha = score;
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+        best_score
+    }
+}
+
+fn has_pieces(board: &Board, color: PieceColor) -> bool {
+    Position::all().iter().any(|position| {
+        board.piece(*position).is_some_and(|piece| {
+            piece.color() == color && !matches!(piece.kind(), PieceKind::Pawn | PieceKind::King)
+        })
+    })
+}
+
+fn capture_gain(board: &Board, board_move: BoardMove) -> Option<i32> {
+    let piece = board.piece(board_move.from)?;
+    let mut gain = match board.piece(board_move.to) {
+        Some(captured) => Some(piece_value(captured.kind())),
+        None if piece.kind() == PieceKind::Pawn && board_move.from.col() != board_move.to.col() => {
+            Some(piece_value(PieceKind::Pawn))
+        }
+        None => None,
+    };
+    if piece.kind() == PieceKind::Pawn && board_move.to.row() == piece.color().opposite().home_row()
+    {
+        gain =
+            Some(gain.unwrap_or(0) + piece_value(PieceKind::Queen) - piece_value(PieceKind::Pawn));
+    }
+    gain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_mate_score, SearchFeatures, Searcher, MATE_SCORE, MAX_PLY};
+    use chess::{board_move, fen, Board, PieceColor};
+
+    #[test]
+    fn finds_mate_in_one() {
+        let board = fen!("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        let result = Searcher::new(&board, PieceColor::White).search(3);
+        assert_eq!(result.best_move, Some(board_move!(a1 a8)));
+        assert_eq!(result.score, MATE_SCORE - 1);
+        assert!(is_mate_score(result.score));
+    }
+
+    #[test]
+    fn finds_mate_in_two() {
+        let board = fen!("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1");
+        let result = Searcher::new(&board, PieceColor::White).search(4);
+        assert_eq!(result.score, MATE_SCORE - 3);
+        assert_eq!(result.pv.len(), 3);
+        assert!(board.is_valid_move(result.pv[0]));
+    }
+
+    #[test]
+    fn avoids_hanging_pieces() {
+        let board = fen!("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1");
+        let result = Searcher::new(&board, PieceColor::White).search(2);
+        assert_ne!(result.best_move, Some(board_move!(d1 d5)));
+    }
+
+    #[test]
+    fn wins_free_material() {
+        let board = fen!("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+        let result = Searcher::new(&board, PieceColor::White).search(3);
+        assert_eq!(result.best_move, Some(board_move!(d1 d5)));
+    }
+
+    #[test]
+    fn table_reduces_nodes() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let with_table = Searcher::new(&board, PieceColor::White).search(3);
+        let without_table = Searcher::new(&board, PieceColor::White)
+            .tt_size(0)
+            .search(3);
+        assert_eq!(with_table.score, without_table.score);
+        assert!(with_table.nodes < without_table.nodes);
+    }
+
+    #[test]
+    fn quiescence_sees_recaptures() {
+        let board = fen!("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1");
+        let result = Searcher::new(&board, PieceColor::White).search(1);
+        assert_ne!(result.best_move, Some(board_move!(d1 d5)));
+        let board = fen!("4k3/8/8/3p4/8/8/8/3QK3 w - - 0 1");
+        let result = Searcher::new(&board, PieceColor::White).search(1);
+        assert_eq!(result.best_move, Some(board_move!(d1 d5)));
+    }
+
+    #[test]
+    fn quiescence_resolves_promotions_and_evasions() {
+        let board = fen!("7k/2P5/8/8/8/8/8/K7 b - - 0 1");
+        let result = Searcher::new(&board, PieceColor::Black).search(1);
+        assert!(result.score <= -700);
+        let board = fen!("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+        let with_evasions = Searcher::new(&board, PieceColor::White).search(1);
+        assert_eq!(with_evasions.score, MATE_SCORE - 1);
+        let without_evasions = Searcher::new(&board, PieceColor::White)
+            .check_evasions(false)
+            .search(1);
+        assert_eq!(without_evasions.best_move, with_evasions.best_move);
+    }
+
+    #[test]
+    fn node_limit_stops_search() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let result = Searcher::new(&board, PieceColor::White)
+            .node_limit(Some(500))
+            .search(10);
+        assert_eq!(result.nodes, 500);
+        assert!(result.depth < 10);
+        assert!(board.is_valid_move(result.best_move.unwrap()));
+        let result = Searcher::new(&board, PieceColor::White)
+            .node_limit(Some(1))
+            .search(10);
+        assert!(board.is_valid_move(result.best_move.unwrap()));
+    }
+
+    #[test]
+    fn root_scores_cover_every_move() {
+        let board = fen!("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+        let scores = Searcher::new(&board, PieceColor::White).root_scores(2);
+        assert_eq!(scores.len(), board.valid_moves(PieceColor::White).len());
+        let (best_move, _) = scores.iter().max_by_key(|(_, score)| *score).unwrap();
+        assert_eq!(*best_move, board_move!(d1 d5));
+    }
+
+    #[test]
+    fn root_scores_only_keep_completed_iterations() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let moves = board.valid_moves(PieceColor::White).len();
+        let scores = Searcher::new(&board, PieceColor::White)
+            .node_limit(Some(3_000))
+            .root_scores(MAX_PLY);
+        assert_eq!(scores.len(), moves);
+        let scores = Searcher::new(&board, PieceColor::White)
+            .node_limit(Some(10))
+            .root_scores(MAX_PLY);
+        assert!(scores.is_empty());
+    }
+
+    #[test]
+    fn eval_noise_is_deterministic_per_seed() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let search = |seed| {
+            Searcher::new(&board, PieceColor::White)
+                .eval_noise(50, seed)
+                .search(2)
+        };
+        assert_eq!(search(1), search(1));
+        let quiet = Searcher::new(&board, PieceColor::White).search(2);
+        assert!((1..10).any(|seed| search(seed).score != quiet.score));
+    }
+
+    #[test]
+    fn selective_search_reduces_nodes() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let selective = Searcher::new(&board, PieceColor::White).search(4);
+        let full = Searcher::new(&board, PieceColor::White)
+            .features(SearchFeatures::none())
+            .search(4);
+        assert!(selective.nodes < full.nodes);
+        assert!(board.is_valid_move(selective.best_move.unwrap()));
+    }
+
+    #[test]
+    fn each_feature_keeps_tactics() {
+        let toggles: [fn(&mut SearchFeatures) -> &mut bool; 5] = [
+            |features| &mut features.null_move,
+            |features| &mut features.late_move_reductions,
+            |features| &mut features.futility_pruning,
+            |features| &mut features.reverse_futility_pruning,
+            |features| &mut features.check_extensions,
+        ];
+        for toggle in toggles {
+            let mut features = SearchFeatures::none();
+            *toggle(&mut features) = true;
+            let board = fen!("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1");
+            let result = Searcher::new(&board, PieceColor::White)
+                .features(features)
+                .search(4);
+            assert_eq!(result.score, MATE_SCORE - 3);
+            let board = fen!("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+            let result = Searcher::new(&board, PieceColor::White)
+                .features(features)
+                .search(3);
+            assert_eq!(result.best_move, Some(board_move!(d1 d5)));
+        }
+    }
+
+    #[test]
+    fn perpetual_check_is_a_draw() {
+        let board = fen!("k7/pp2q3/2Q5/2RR4/8/8/6P1/7K b - - 0 1");
+        let result = Searcher::new(&board, PieceColor::Black).search(6);
+        assert_eq!(result.best_move, Some(board_move!(e7 e1)));
+        assert_eq!(result.score, 0);
+    }
+
+    #[test]
+    fn searches_the_given_color_when_it_is_not_to_move() {
+        let board = Board::new();
+        let result = Searcher::new(&board, PieceColor::Black).search(2);
+        let reply = result.best_move.unwrap();
+        assert_eq!(
+            board.piece(reply.from).map(|piece| piece.color()),
+            Some(PieceColor::Black)
+        );
+        let mut passed = board;
+        passed.null_move();
+        assert!(passed.is_valid_move(reply));
+        assert_eq!(
+            crate::plan_move(&board, PieceColor::Black)
+                .map(|board_move| passed.is_valid_move(board_move)),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn stalemate_is_a_draw() {
+        let board = fen!("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1");
+        let result = Searcher::new(&board, PieceColor::Black).search(2);
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, 0);
+    }
+}
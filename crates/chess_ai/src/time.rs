@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+const MOVE_OVERHEAD: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeControl {
+    MoveTime(Duration),
+    Clock {
+        remaining: Duration,
+        increment: Duration,
+        moves_to_go: Option<u32>,
+    },
+}
+
+impl TimeControl {
+    pub fn budget(&self) -> Duration {
+        match *self {
+            TimeControl::MoveTime(time) => time,
+            TimeControl::Clock {
+                remaining,
+                increment,
+                moves_to_go,
+            } => {
+                let moves = moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+                let budget = remaining / moves + increment * 3 / 4;
+                budget.min(remaining.saturating_sub(MOVE_OVERHEAD) / 2)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeControl;
+    use crate::Searcher;
+    use chess::{fen, PieceColor};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn clock_budget() {
+        let clock = |remaining, increment, moves_to_go| TimeControl::Clock {
+            remaining: Duration::from_secs(remaining),
+            increment: Duration::from_secs(increment),
+            moves_to_go,
+        };
+        assert_eq!(
+            TimeControl::MoveTime(Duration::from_secs(2)).budget(),
+            Duration::from_secs(2)
+        );
+        assert_eq!(clock(60, 0, Some(10)).budget(), Duration::from_secs(6));
+        assert_eq!(clock(300, 0, None).budget(), Duration::from_secs(10));
+        assert_eq!(clock(60, 4, Some(20)).budget(), Duration::from_secs(6));
+        assert!(clock(10, 0, Some(1)).budget() < Duration::from_secs(5));
+        assert_eq!(clock(0, 0, None).budget(), Duration::ZERO);
+    }
+
+    #[test]
+    fn time_control_stops_search() {
+        let board = fen!("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4");
+        let start = Instant::now();
+        let result = Searcher::new(&board, PieceColor::White)
+            .time_control(Some(TimeControl::MoveTime(Duration::from_millis(200))))
+            .search(64);
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(result.depth < 64);
+        assert!(board.is_valid_move(result.best_move.unwrap()));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::search::{is_mate_score, MATE_SCORE};
+use chess::BoardMove;
+use std::mem::size_of;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TtEntry {
+    pub key: u64,
+    pub depth: u32,
+    pub bound: Bound,
+    pub score: i32,
+    pub best_move: Option<BoardMove>,
+}
+
+pub struct TranspositionTable {
+    entries: Vec<Option<TtEntry>>,
+}
+
+impl TranspositionTable {
+    pub fn new(megabytes: usize) -> Self {
+        let len = megabytes * 1024 * 1024 / size_of::<Option<TtEntry>>();
+        Self {
+            entries: vec![None; len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.fill(None);
+    }
+
+    pub fn probe(&self, key: u64) -> Option<TtEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        self.entries[self.index(key)].filter(|entry| entry.key == key)
+    }
+
+    pub fn store(&mut self, entry: TtEntry) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let index = self.index(entry.key);
+        let slot = &mut self.entries[index];
+        if slot.map_or(true, |existing| {
+            existing.key != entry.key || existing.depth <= entry.depth
+        }) {
+            *slot = Some(entry);
+        }
+    }
+
+    fn index(&self, key: u64) -> usize {
+        (key % self.entries.len() as u64) as usize
+    }
+}
+
+pub(crate) fn score_to_tt(score: i32, ply: u32) -> i32 {
+    if is_mate_score(score) {
+        score + score.signum() * ply as i32
+    } else {
+        score
+    }
+}
+
+pub(crate) fn score_from_tt(score: i32, ply: u32) -> i32 {
+    if is_mate_score(score) {
+        (score - score.signum() * ply as i32).clamp(-MATE_SCORE, MATE_SCORE)
+    } else {
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{score_from_tt, score_to_tt, Bound, TranspositionTable, TtEntry};
+    use crate::search::MATE_SCORE;
+    use chess::board_move;
+
+    fn entry(key: u64, depth: u32) -> TtEntry {
+        TtEntry {
+            key,
+            depth,
+            bound: Bound::Exact,
+            score: 10,
+            best_move: Some(board_move!(e2 e4)),
+        }
+    }
+
+    #[test]
+    fn table_size_in_megabytes() {
+        let table = TranspositionTable::new(1);
+        assert!(table.len() > 1000);
+        assert!(table.len() * std::mem::size_of::<Option<TtEntry>>() <= 1024 * 1024);
+        let mut table = TranspositionTable::new(0);
+        assert!(table.is_empty());
+        table.store(entry(1, 1));
+        assert_eq!(table.probe(1), None);
+    }
+
+    #[test]
+    fn store_and_replace() {
+        let mut table = TranspositionTable::new(1);
+        let len = table.len() as u64;
+        table.store(entry(5, 3));
+        assert_eq!(table.probe(5), Some(entry(5, 3)));
+        assert_eq!(table.probe(5 + len), None);
+        table.store(entry(5, 1));
+        assert_eq!(table.probe(5).unwrap().depth, 3);
+        table.store(entry(5 + len, 1));
+        assert_eq!(table.probe(5), None);
+        assert_eq!(table.probe(5 + len), Some(entry(5 + len, 1)));
+        table.clear();
+        assert_eq!(table.probe(5 + len), None);
+    }
+
+    #[test]
+    fn mate_scores_are_ply_relative() {
+        let score = MATE_SCORE - 5;
+        assert_eq!(score_from_tt(score_to_tt(score, 3), 3), score);
+        assert_eq!(score_from_tt(score_to_tt(score, 3), 1), MATE_SCORE - 3);
+        assert_eq!(score_to_tt(-score, 2), -(MATE_SCORE - 3));
+        assert_eq!(score_to_tt(120, 7), 120);
+    }
+}
@@ -1,11 +1,17 @@
 use crate::common::prelude::*;
 use bevy::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+use std::time::Duration;
 
 use super::{
     end_game::EndGameSpawnEvent,
     settings::{GameControl, GameSettings},
 };
 
+const AI_MOVE_TIME: Duration = Duration::from_secs(1);
+const AI_NODE_LIMIT: u64 = 200_000;
+const AI_EVAL_NOISE: i32 = 20;
+
 pub struct BoardPlugin;
 
 impl Plugin for BoardPlugin {
@@ -13,6 +19,7 @@ impl Plugin for BoardPlugin {
         app.add_event::<BoardSpawnEvent>()
             .add_system(board_spawn)
             .add_system(board_update_pieces)
+            .add_system(board_take_back.before(board_update_ai))
             .add_system(board_update_ai)
             .add_system(board_update_player)
             .add_system(board_resize);
@@ -28,6 +35,11 @@ pub struct Board {
     dirty: bool,
     turn: chess::PieceColor,
     stopped: bool,
+    history: Vec<chess::Board>,
+    ai_rng: StdRng,
+    ai_config: chess_ai::AiConfig,
+    ai_search: Option<chess_ai::SearchHandle>,
+    ai_paused: bool,
 }
 
 #[derive(Component)]
@@ -64,12 +76,37 @@ impl BoardPiece {
     }
 }
 
+// The seed picks the evaluation noise, so it decides between close moves.
+// Seeded games search a fixed number of nodes instead of a fixed time so that
+// they replay move for move.
+fn ai_config(seeded: bool) -> chess_ai::AiConfig {
+    let config = chess_ai::AiConfig {
+        depth: chess_ai::MAX_PLY,
+        eval_noise: AI_EVAL_NOISE,
+        ..chess_ai::AiConfig::default()
+    };
+    if seeded {
+        chess_ai::AiConfig {
+            node_limit: Some(AI_NODE_LIMIT),
+            ..config
+        }
+    } else {
+        chess_ai::AiConfig {
+            time_control: Some(chess_ai::TimeControl::MoveTime(AI_MOVE_TIME)),
+            ..config
+        }
+    }
+}
+
 pub fn board_spawn(
     mut ev_board_spawn: EventReader<BoardSpawnEvent>,
     mut command: Commands,
     game_settings: Res<GameSettings>,
 ) {
     for _ in ev_board_spawn.iter() {
+        let ai_seed = game_settings.ai_seed().unwrap_or_else(rand::random);
+        info!("AI seed: {}", ai_seed);
+        let ai_config = ai_config(game_settings.ai_seed().is_some());
         command
             .spawn_bundle(TransformBundle {
                 local: Transform::from_translation(Vec3::ZERO)
@@ -82,6 +119,11 @@ pub fn board_spawn(
                 dirty: true,
                 turn: chess::PieceColor::White,
                 stopped: false,
+                history: vec![],
+                ai_rng: StdRng::seed_from_u64(ai_seed),
+                ai_config,
+                ai_search: None,
+                ai_paused: false,
             })
             .with_children(|parent| {
                 let col_count = chess::Position::col_count() as f32;
@@ -110,10 +152,10 @@ pub fn board_spawn(
                     } else {
                         (chess::Position::row_count() - position.row() - 1) as f32
                     };
-                    let color = if position.col() % 2 != position.row() % 2 {
-                        Color::rgb_u8(118, 134, 171)
-                    } else {
+                    let color = if position.is_light_square() {
                         Color::rgb_u8(200, 200, 200)
+                    } else {
+                        Color::rgb_u8(118, 134, 171)
                     };
                     parent
                         .spawn_bundle(SpriteBundle {
@@ -203,12 +245,14 @@ pub fn board_update_pieces(
 #[derive(Default)]
 pub struct BoardUpdateAiState {
     timer: f32,
+    depth: u32,
 }
 
 pub fn board_update_ai(
     mut board_query: Query<&mut Board>,
     mut state: Local<BoardUpdateAiState>,
     time: Res<Time>,
+    keys: Res<Input<KeyCode>>,
     settings: Res<GameSettings>,
 ) {
     let turn_timer = {
@@ -220,22 +264,98 @@ pub fn board_update_ai(
             false
         }
     };
-    if turn_timer {
-        for mut board in board_query.iter_mut() {
-            if let GameControl::Ai = settings.control(board.turn) {
-                if board.stopped {
-                    continue;
+    for mut board in board_query.iter_mut() {
+        let board = &mut *board;
+        if board.stopped {
+            board.ai_search = None;
+            continue;
+        }
+        if keys.just_pressed(KeyCode::Space) {
+            if board.ai_search.take().is_some() {
+                board.ai_paused = true;
+                info!("AI paused");
+            } else if board.ai_paused {
+                board.ai_paused = false;
+                info!("AI resumed");
+            }
+        }
+        if board.ai_paused {
+            continue;
+        }
+        if let GameControl::Ai = settings.control(board.turn) {
+            match board.ai_search.as_mut() {
+                None => {
+                    if turn_timer {
+                        let config = board.ai_config;
+                        let searcher = config.searcher(&board.board, board.turn, &mut board.ai_rng);
+                        board.ai_search =
+                            Some(chess_ai::SearchHandle::spawn(searcher, config.depth));
+                        state.depth = 0;
+                    }
                 }
-                if let Some(ai_move) = chess_ai::plan_move(&board.board, board.turn) {
-                    board.board.apply_move(ai_move);
-                    board.turn = board.turn.opposite();
-                    board.dirty = true;
+                Some(search) => {
+                    let progress = search.poll();
+                    if progress.depth > state.depth {
+                        state.depth = progress.depth;
+                        info!(
+                            "AI thinking: depth {} score {} nodes {} best {}",
+                            progress.depth,
+                            progress.score,
+                            progress.nodes,
+                            progress
+                                .best_move
+                                .map(|best_move| best_move.coordinate())
+                                .unwrap_or_default()
+                        );
+                    }
+                    if search.is_finished() {
+                        let result = board.ai_search.take().map(|search| search.wait());
+                        if let Some(ai_move) = result.and_then(|result| result.best_move) {
+                            board.history.push(board.board);
+                            board.board.apply_move(ai_move);
+                            board.turn = board.turn.opposite();
+                            board.dirty = true;
+                        }
+                        state.timer = 0.;
+                    }
                 }
             }
         }
     }
 }
 
+pub fn board_take_back(
+    mut board_query: Query<&mut Board>,
+    keys: Res<Input<KeyCode>>,
+    settings: Res<GameSettings>,
+) {
+    if !keys.just_pressed(KeyCode::Back) {
+        return;
+    }
+    let has_player = [chess::PieceColor::White, chess::PieceColor::Black]
+        .iter()
+        .any(|color| matches!(settings.control(*color), GameControl::Player));
+    for mut board in board_query.iter_mut() {
+        let board = &mut *board;
+        if board.stopped {
+            continue;
+        }
+        // Dropping the handle stops a search that is still running.
+        board.ai_search = None;
+        // Take back until a player is to move again, or a single move when
+        // the AI plays both sides.
+        while let Some(previous) = board.history.pop() {
+            board.board = previous;
+            board.turn = previous.turn();
+            board.dirty = true;
+            if !has_player || matches!(settings.control(board.turn), GameControl::Player) {
+                break;
+            }
+        }
+        board.ai_paused = matches!(settings.control(board.turn), GameControl::Ai);
+    }
+}
+
 #[derive(Default)]
 pub struct BoardUpdatePlayerState {
     drag: Option<Entity>,
@@ -295,7 +415,9 @@ pub fn board_update_player(
                         to: hovered_position,
                     };
                     if let Ok(mut board) = board_query.get_mut(piece.board_entity) {
+                        let previous = board.board;
                         if board.board.apply_move(board_move) {
+                            board.history.push(previous);
                             reset_position = false;
                             board.turn = board.turn.opposite();
                             board.dirty = true;
@@ -1,10 +1,26 @@
-#[derive(Default)]
 pub struct GameSettings {
     white_control: GameControl,
     black_control: GameControl,
+    ai_seed: Option<u64>,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            white_control: GameControl::default(),
+            black_control: GameControl::default(),
+            ai_seed: std::env::var("CHESS_AI_SEED")
+                .ok()
+                .and_then(|seed| seed.parse().ok()),
+        }
+    }
 }
 
 impl GameSettings {
+    pub fn ai_seed(&self) -> Option<u64> {
+        self.ai_seed
+    }
+
     pub fn control(&self, color: chess::PieceColor) -> &GameControl {
         match color {
             chess::PieceColor::White => &self.white_control,